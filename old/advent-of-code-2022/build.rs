@@ -0,0 +1,58 @@
+use std::{env, fmt::Write, fs, path::PathBuf};
+
+/// generate the registry of the solutions in `src/bin`, so the aoc binary can run them in-process
+fn main() {
+    let bin = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    println!("cargo:rerun-if-changed={}", bin.display());
+
+    let mut days = fs::read_dir(&bin)
+        .unwrap()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let (year, day) = name.strip_suffix(".rs")?.split_once('_')?;
+
+            Some((
+                year.parse::<u32>().ok()?,
+                day.parse::<u32>().ok()?,
+                entry.path(),
+            ))
+        })
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut modules = String::new();
+    let mut arms = String::new();
+    for (year, day, path) in &days {
+        let module = format!("day_{year}_{day:0>2}");
+
+        // the days are tested and linted as their own bins
+        writeln!(modules, "#[cfg(not(test))]").unwrap();
+        writeln!(modules, "#[path = {:?}]", path.display().to_string()).unwrap();
+        writeln!(modules, "#[allow(dead_code, unused, clippy::all)]").unwrap();
+        writeln!(modules, "mod {module};").unwrap();
+
+        writeln!(arms, "        #[cfg(not(test))]").unwrap();
+        writeln!(
+            arms,
+            "        ({year}, {day}) => Some(Solution {{ \
+             part_1: |input| {module}::part_1(input).into_answer(), \
+             part_2: |input| {module}::part_2(input).into_answer() }}),"
+        )
+        .unwrap();
+    }
+
+    let registry = format!(
+        "{modules}
+/// solution of a puzzle that is compiled into this binary
+pub fn find_solution(PuzzleId {{ year, day }}: PuzzleId) -> Option<Solution> {{
+    match (year, day) {{
+{arms}        _ => None,
+    }}
+}}
+"
+    );
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out, registry).unwrap();
+}
@@ -0,0 +1,117 @@
+use std::{collections::BTreeMap, fmt, num::ParseIntError, str::FromStr};
+
+/// answers accepted by adventofcode.com for the puzzles of a year
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u32, part: u32, answer: String) {
+        self.answers.insert((day, part), answer);
+    }
+
+    /// number of parts with a known answer for a day
+    pub fn parts(&self, day: u32) -> usize {
+        self.answers.range((day, 0)..(day + 1, 0)).count()
+    }
+
+    /// whether the answers of all parts solved with `stars` are known
+    ///
+    /// Day 25 only has one puzzle, its second star is given for the other 49.
+    pub fn is_complete(&self, day: u32, stars: usize) -> bool {
+        let parts = if day == 25 { stars.min(1) } else { stars };
+        self.parts(day) >= parts
+    }
+
+    /// all answers as `(day, part, answer)`, ordered by day and part
+    pub fn iter(&self) -> impl Iterator<Item = (u32, u32, &str)> {
+        self.answers
+            .iter()
+            .map(|(&(day, part), answer)| (day, part, answer.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.answers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.answers.is_empty()
+    }
+}
+
+/// one line per answer with day, part and answer separated by spaces, e.g. `17 2 1514285714288`
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (day, part, answer) in self.iter() {
+            writeln!(f, "{day} {part} {answer}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = Answers::default();
+
+        for line in s.lines().filter(|line| !line.is_empty()) {
+            let mut fields = line.splitn(3, ' ');
+            let day = fields.next().unwrap_or_default().parse()?;
+            let part = fields.next().unwrap_or_default().parse()?;
+            let answer = fields.next().unwrap_or_default();
+
+            answers.insert(day, part, answer.to_string());
+        }
+
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parts() {
+        let mut answers = Answers::default();
+        answers.insert(1, 1, "24000".into());
+        answers.insert(1, 2, "45000".into());
+        answers.insert(2, 1, "15".into());
+
+        assert_eq!(answers.parts(1), 2);
+        assert_eq!(answers.parts(2), 1);
+        assert_eq!(answers.parts(3), 0);
+        assert_eq!(answers.get(1, 2), Some("45000"));
+        assert_eq!(answers.get(2, 2), None);
+        assert!(answers.is_complete(1, 2));
+        assert!(!answers.is_complete(2, 2));
+    }
+
+    #[test]
+    fn test_is_complete_day_25() {
+        let mut answers = Answers::default();
+        assert!(answers.is_complete(25, 0));
+        assert!(!answers.is_complete(25, 2));
+
+        answers.insert(25, 1, "6011069".into());
+        assert!(answers.is_complete(25, 1));
+        assert!(answers.is_complete(25, 2));
+    }
+
+    #[test]
+    fn test_to_string() {
+        let answers: Answers = "1 1 24000\n1 2 45000\n5 1 CMZ\n".parse().unwrap();
+
+        assert_eq!(answers.len(), 3);
+        assert_eq!(answers.get(5, 1), Some("CMZ"));
+        assert_eq!(answers.to_string(), "1 1 24000\n1 2 45000\n5 1 CMZ\n");
+        assert!("x 1 24000".parse::<Answers>().is_err());
+    }
+}
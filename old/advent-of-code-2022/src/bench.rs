@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    hint::black_box,
+    io,
+    path::Path,
+    time::{Duration, Instant},
+};
+
+/// set by `aoc bench` to the number of runs, solutions then print measurements instead of answers
+pub const BENCH_VARIABLE: &str = "AOC_BENCH";
+
+/// measuring stops after this time, as long as a few runs are done
+const MAX_BENCH_TIME: Duration = Duration::from_secs(10);
+const MIN_RUNS: usize = 3;
+
+/// timings of repeated runs of a puzzle part
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct Measurement {
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Measurement {
+    /// summarize the durations of the runs
+    pub fn from_samples(mut samples: Vec<Duration>) -> Measurement {
+        samples.sort();
+
+        // nearest-rank percentile
+        let percentile = |p: usize| {
+            let rank = (p * samples.len()).div_ceil(100).max(1);
+            samples[rank - 1].as_nanos() as u64
+        };
+
+        Measurement {
+            runs: samples.len(),
+            min_ns: percentile(0),
+            median_ns: percentile(50),
+            p95_ns: percentile(95),
+        }
+    }
+
+    pub fn median(&self) -> Duration {
+        Duration::from_nanos(self.median_ns)
+    }
+}
+
+/// measurements of the parts of a puzzle, keyed by `part_1` and `part_2`
+pub type Baseline = BTreeMap<String, Measurement>;
+
+/// number of runs requested with `AOC_BENCH`, `None` if not benchmarking
+pub fn requested_runs() -> Option<usize> {
+    env::var(BENCH_VARIABLE).ok()?.parse().ok()
+}
+
+/// run `f` once as warm-up and then up to `runs` times
+pub fn measure<T>(runs: usize, mut f: impl FnMut() -> T) -> Measurement {
+    black_box(f());
+
+    let started = Instant::now();
+    let mut samples = Vec::with_capacity(runs);
+
+    while samples.len() < runs.max(1)
+        && (samples.len() < MIN_RUNS || started.elapsed() < MAX_BENCH_TIME)
+    {
+        let start = Instant::now();
+        black_box(f());
+        samples.push(start.elapsed());
+    }
+
+    Measurement::from_samples(samples)
+}
+
+/// benchmark both parts of a solution and print the measurements as one line of json
+pub fn run_parts<A, B>(runs: usize, part_1: impl FnMut() -> A, part_2: impl FnMut() -> B) {
+    let baseline = Baseline::from([
+        (String::from("part_1"), measure(runs, part_1)),
+        (String::from("part_2"), measure(runs, part_2)),
+    ]);
+
+    println!("{}", serde_json::to_string(&baseline).unwrap());
+}
+
+pub fn load_baseline(path: &Path) -> Result<Option<Baseline>, io::Error> {
+    match fs::read_to_string(path) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn save_baseline(path: &Path, baseline: &Baseline) -> Result<(), io::Error> {
+    if let Some(folder) = path.parent() {
+        fs::create_dir_all(folder)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(baseline)? + "\n")
+}
+
+/// ratio of the current median to the baseline median, for parts whose ratio exceeds `1 + threshold`
+pub fn regressions(baseline: &Baseline, current: &Baseline, threshold: f64) -> Vec<(String, f64)> {
+    current
+        .iter()
+        .filter_map(|(part, measurement)| {
+            let before = baseline.get(part)?.median_ns.max(1) as f64;
+            let ratio = measurement.median_ns as f64 / before;
+
+            (ratio > 1.0 + threshold).then(|| (part.clone(), ratio))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(median_ns: u64) -> Measurement {
+        Measurement {
+            runs: 10,
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn test_from_samples() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+
+        assert_eq!(
+            Measurement::from_samples(samples),
+            Measurement {
+                runs: 20,
+                min_ns: 1_000_000,
+                median_ns: 10_000_000,
+                p95_ns: 19_000_000,
+            }
+        );
+        assert_eq!(
+            Measurement::from_samples(vec![Duration::from_nanos(7)]).p95_ns,
+            7
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+
+        let measurement = measure(5, || calls += 1);
+
+        assert_eq!(measurement.runs, 5);
+        assert_eq!(calls, 6);
+    }
+
+    #[test]
+    fn test_regressions() {
+        let baseline = Baseline::from([
+            (String::from("part_1"), measurement(100)),
+            (String::from("part_2"), measurement(100)),
+        ]);
+        let current = Baseline::from([
+            (String::from("part_1"), measurement(110)),
+            (String::from("part_2"), measurement(200)),
+        ]);
+
+        assert_eq!(
+            regressions(&baseline, &current, 0.2),
+            vec![(String::from("part_2"), 2.0)]
+        );
+        assert!(regressions(&baseline, &baseline, 0.2).is_empty());
+        assert!(regressions(&Baseline::new(), &current, 0.2).is_empty());
+    }
+}
@@ -1,8 +1,17 @@
-use aoc::client::get_input;
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 1)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
@@ -16,13 +25,13 @@ fn calculate_sums(input: &str) -> Vec<u32> {
         .collect()
 }
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     let sums = calculate_sums(input);
 
     sums.iter().max().unwrap().to_string()
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     let mut calories = calculate_sums(input);
 
     calories.sort();
@@ -1,8 +1,17 @@
-use aoc::client::get_input;
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use std::error::Error;
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 2)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
@@ -69,7 +78,7 @@ fn compare_hands(opponent: Hand, own: Hand) -> Outcome {
     }
 }
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     let score = parse_input(input)
         .iter()
         .map(|hands| {
@@ -94,7 +103,7 @@ fn parse_input_part_2(input: &str) -> Vec<(Hand, Outcome)> {
         .collect()
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     let score: usize = parse_input_part_2(input).iter().map(|game| match game {
         (hand, Outcome::Draw) => *hand as usize,
         (Hand::Rock, Outcome::Loss) => Hand::Scissor as usize,
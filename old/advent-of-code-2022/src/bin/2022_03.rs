@@ -1,8 +1,17 @@
-use aoc::client::get_input;
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use std::{collections::HashSet, error::Error};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 3)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
@@ -11,7 +20,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 const ITEMS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     let value = input
         .lines()
         .map(|l| -> usize {
@@ -31,7 +40,7 @@ fn part_1(input: &str) -> String {
     format!("{value}")
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     let lines = input.lines();
 
     let mut sum = 0;
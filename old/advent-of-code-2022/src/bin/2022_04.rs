@@ -1,9 +1,18 @@
-use aoc::client::get_input;
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use regex::Regex;
 use std::{error::Error, ops::RangeInclusive};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 4)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
@@ -31,7 +40,7 @@ fn parse_input(input: &str) -> Vec<RangePair> {
     range_pairs
 }
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     let overlaps = parse_input(input)
         .iter()
         .filter(|ranges| {
@@ -43,7 +52,7 @@ fn part_1(input: &str) -> String {
     format!("{overlaps}")
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     let overlaps = parse_input(input)
         .iter()
         .filter(|ranges| {
@@ -1,9 +1,18 @@
-use aoc::client::get_input;
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use regex::Regex;
 use std::{error::Error, str::FromStr};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 5)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
@@ -90,7 +99,7 @@ fn parse_input(input: &str) -> (Cargo, Vec<Move>) {
     );
 }
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     let (mut cargo, moves) = parse_input(input);
 
     for m in moves {
@@ -107,7 +116,7 @@ fn part_1(input: &str) -> String {
         .collect()
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     let (mut cargo, moves) = parse_input(input);
 
     for m in moves {
@@ -1,4 +1,7 @@
-use aoc::client::get_input;
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use std::{
     collections::{HashSet, VecDeque},
     error::Error,
@@ -6,6 +9,12 @@ use std::{
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 6)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
@@ -34,11 +43,11 @@ fn find_marker_end(message: &str, marker_length: usize) -> usize {
         .count()
 }
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     format!("{}", find_marker_end(input, 4))
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     format!("{}", find_marker_end(input, 14))
 }
 
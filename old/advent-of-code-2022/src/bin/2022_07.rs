@@ -1,4 +1,7 @@
-use aoc::{client::get_input};
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+};
 use std::{cell::RefCell, error::Error, rc::Rc, collections::HashMap};
 
 #[derive(Debug, PartialEq, Eq)]
@@ -58,7 +61,7 @@ fn parse_input(input: &str) -> Result<HashMap<&str, Rc<RefCell<Folder>>>, Box<dy
             // TODO add directory
         } else if let Some(captures) = file_matcher.captures(line) {
             let file_size = captures.get(1).unwrap().as_str().parse::<usize>();
-            current_node.get_mut().increase_size(file_size.unwrap());
+            current_node.borrow_mut().increase_size(file_size.unwrap());
         }
     });
 
@@ -75,19 +78,25 @@ fn parse_input(input: &str) -> Result<HashMap<&str, Rc<RefCell<Folder>>>, Box<dy
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 7)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input));
     println!("solution part 2: {}", part_2(&input));
 
     Ok(())
 }
 
-fn part_1(input: &str) -> String {
+pub fn part_1(input: &str) -> String {
     let fs_nodes = parse_input(&input);
 
     unimplemented!()
 }
 
-fn part_2(input: &str) -> String {
+pub fn part_2(input: &str) -> String {
     unimplemented!();
 }
 
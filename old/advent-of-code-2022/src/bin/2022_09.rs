@@ -1,4 +1,8 @@
-use aoc::{client::get_input, point::Point};
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+    point::Point,
+};
 use std::{collections::HashSet, error::Error, iter};
 
 #[derive(Debug)]
@@ -54,6 +58,12 @@ impl Default for Rope {
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 9)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input)?);
     println!("solution part 2: {}", part_2(&input)?);
 
@@ -80,7 +90,7 @@ fn parse_input(input: &str) -> Vec<Point> {
         .collect()
 }
 
-fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
+pub fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
     let moves = parse_input(input);
     let mut rope = Rope::default();
     let mut visited = HashSet::<Point>::new();
@@ -95,7 +105,7 @@ fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
     Ok(format!("{}", visited.len()))
 }
 
-fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
+pub fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
     let moves = parse_input(input);
     let mut rope = Rope::new(10);
     let mut visited = HashSet::<Point>::new();
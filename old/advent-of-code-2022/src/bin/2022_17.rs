@@ -1,8 +1,18 @@
-use aoc::{client::get_input, point::Point};
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+    point::Point,
+};
 use std::{collections::HashSet, error::Error};
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let input = get_input(2022, 17)?.replace('\n', "");
+    let input = get_input(2022, 17)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input)?);
     println!("solution part 2: {}", part_2(&input)?);
 
@@ -102,6 +112,7 @@ fn build_tower(input: &str, blocks: usize) -> (usize, Vec<usize>) {
     let mut fixed_blocks: HashSet<Point> = HashSet::new();
 
     let mut movements = input
+        .trim()
         .chars()
         .map(|c| match c {
             '<' => -1,
@@ -145,11 +156,11 @@ fn build_tower(input: &str, blocks: usize) -> (usize, Vec<usize>) {
     (height, diffs)
 }
 
-fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
+pub fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
     Ok(format!("{}", &build_tower(input, 2022).0))
 }
 
-fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
+pub fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
     let num_blocks = 1000000000000;
     let (_height, diffs) = build_tower(input, 10000);
 
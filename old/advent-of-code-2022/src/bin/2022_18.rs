@@ -1,4 +1,8 @@
-use aoc::{client::get_input, point::Point};
+use aoc::{
+    bench::{requested_runs, run_parts},
+    client::get_input,
+    point::Point,
+};
 use std::{
     collections::{HashSet, VecDeque},
     error::Error,
@@ -7,6 +11,12 @@ use std::{
 
 fn main() -> Result<(), Box<dyn Error>> {
     let input = get_input(2022, 18)?;
+
+    if let Some(runs) = requested_runs() {
+        run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+
     println!("solution part 1: {}", part_1(&input)?);
     println!("solution part 2: {}", part_2(&input)?);
 
@@ -22,7 +32,7 @@ const NEIGHBOURS: &[Point] = &[
     Point { x: 0, y: 0, z: -1 },
 ];
 
-fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
+pub fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
     let mut points: HashSet<Point> = HashSet::new();
     let mut connections = 0;
     input
@@ -40,7 +50,7 @@ fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
     Ok(format!("{}", points.len() * 6 - connections * 2))
 }
 
-fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
+pub fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
     let points: HashSet<Point> =
         HashSet::from_iter(input.lines().map(|line| Point::from_str(line).unwrap()));
     let extent = points
@@ -0,0 +1,137 @@
+use crate::{
+    bench::format_nanos,
+    runner::{build_solutions, run_solution},
+};
+use aoc::{answers::Answers, client::get_answers, puzzle::PuzzleId};
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    error::Error,
+    fmt::Display,
+    time::Duration,
+};
+
+/// how an answer compares to the accepted one
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Check {
+    Correct,
+    Wrong,
+    /// no accepted answer is known
+    Unknown,
+    /// the solution did not print an answer
+    Missing,
+}
+
+impl Check {
+    pub fn new(answer: Option<&str>, accepted: Option<&str>) -> Check {
+        match (answer, accepted) {
+            (None, _) => Check::Missing,
+            (Some(_), None) => Check::Unknown,
+            (Some(answer), Some(accepted)) if answer == accepted => Check::Correct,
+            (Some(_), Some(_)) => Check::Wrong,
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Check::Correct => write!(f, "ok"),
+            Check::Wrong => write!(f, "WRONG"),
+            Check::Unknown => write!(f, "?"),
+            Check::Missing => write!(f, "MISSING"),
+        }
+    }
+}
+
+/// counts of the checked parts
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unknown: usize,
+    pub total_time: Duration,
+}
+
+impl Summary {
+    fn add(&mut self, check: Check) {
+        match check {
+            Check::Correct => self.passed += 1,
+            Check::Unknown => self.unknown += 1,
+            Check::Wrong | Check::Missing => self.failed += 1,
+        }
+    }
+}
+
+/// run the solutions of all puzzles and compare their answers to the accepted ones
+pub fn run_all(puzzles: &[PuzzleId]) -> Result<Summary, Box<dyn Error>> {
+    build_solutions()?;
+
+    let mut accepted = BTreeMap::<u32, Answers>::new();
+    let mut summary = Summary::default();
+
+    for &puzzle in puzzles {
+        let answers = match accepted.entry(puzzle.year) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(get_answers(puzzle.year)?),
+        };
+
+        let run = match run_solution(puzzle) {
+            Ok(run) => run,
+            Err(error) => {
+                println!("{puzzle:<8} {error}");
+                summary.add(Check::Missing);
+                summary.add(Check::Missing);
+                continue;
+            }
+        };
+        summary.total_time += run.elapsed;
+
+        for (part, answer) in (1..=2).zip(&run.answers) {
+            let accepted = answers.get(puzzle.day, part);
+            let check = Check::new(answer.as_deref(), accepted);
+            summary.add(check);
+
+            // the solution runs both parts in one process, so the time is shown once
+            let (label, time) = match part {
+                1 => (
+                    puzzle.to_string(),
+                    format_nanos(run.elapsed.as_nanos() as u64),
+                ),
+                _ => (String::new(), String::new()),
+            };
+            let expected = match (check, accepted) {
+                (Check::Wrong | Check::Missing, Some(accepted)) => format!("expected {accepted}"),
+                _ => String::new(),
+            };
+
+            let row = format!(
+                "{label:<8} part {part}  {:<20} {time:>10}  {check:<7} {expected}",
+                answer.as_deref().unwrap_or("-"),
+            );
+            println!("{}", row.trim_end());
+        }
+    }
+
+    println!(
+        "total {}: {} passed, {} failed, {} without accepted answer",
+        format_nanos(summary.total_time.as_nanos() as u64),
+        summary.passed,
+        summary.failed,
+        summary.unknown
+    );
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check() {
+        assert_eq!(Check::new(Some("42"), Some("42")), Check::Correct);
+        assert_eq!(Check::new(Some("41"), Some("42")), Check::Wrong);
+        assert_eq!(Check::new(Some("42"), None), Check::Unknown);
+        assert_eq!(Check::new(None, Some("42")), Check::Missing);
+    }
+}
@@ -0,0 +1,86 @@
+use aoc::{
+    bench::{load_baseline, regressions, save_baseline, Baseline, BENCH_VARIABLE},
+    client::cache_root,
+    puzzle::PuzzleId,
+};
+use std::{error::Error, path::PathBuf, process::Command, time::Duration};
+
+fn baseline_path(PuzzleId { year, day }: PuzzleId) -> PathBuf {
+    cache_root()
+        .join("bench")
+        .join(format!("{year}_{day:0>2}.json"))
+}
+
+/// run the solution in benchmark mode, it prints its measurements as the last line
+fn run_solution(PuzzleId { year, day }: PuzzleId, runs: usize) -> Result<Baseline, Box<dyn Error>> {
+    let output = Command::new("cargo")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["run", "--release", "--bin", &format!("{year}_{day:0>2}")])
+        .env(BENCH_VARIABLE, runs.to_string())
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr.lines().last().unwrap_or("no output");
+
+        return Err(format!("solution failed: {message}").into());
+    }
+
+    let stdout = String::from_utf8(output.stdout)?;
+    let json = stdout.lines().last().unwrap_or_default();
+
+    serde_json::from_str(json)
+        .map_err(|_| "no measurements printed, the solution lacks the bench hook".into())
+}
+
+pub fn format_nanos(nanos: u64) -> String {
+    format!("{:.2?}", Duration::from_nanos(nanos))
+}
+
+/// benchmark a puzzle and compare it to its baseline, returns the number of regressions
+///
+/// The measurements are saved as new baseline if there is none yet, or if `save` is set.
+pub fn bench_puzzle(
+    puzzle: PuzzleId,
+    runs: usize,
+    threshold: f64,
+    save: bool,
+) -> Result<usize, Box<dyn Error>> {
+    let current = run_solution(puzzle, runs)?;
+    let path = baseline_path(puzzle);
+    let baseline = load_baseline(&path)?;
+
+    for (part, measurement) in &current {
+        let change = baseline
+            .as_ref()
+            .and_then(|baseline| baseline.get(part))
+            .map(|before| {
+                let ratio = measurement.median_ns as f64 / before.median_ns.max(1) as f64;
+                format!("{:+.0}%", (ratio - 1.0) * 100.0)
+            })
+            .unwrap_or_default();
+
+        println!(
+            "{puzzle:<8} {part}  min {:>10}  median {:>10}  p95 {:>10}  {change}",
+            format_nanos(measurement.min_ns),
+            format_nanos(measurement.median_ns),
+            format_nanos(measurement.p95_ns),
+        );
+    }
+
+    let regressions = match &baseline {
+        Some(baseline) => regressions(baseline, &current, threshold),
+        None => Vec::new(),
+    };
+
+    for (part, ratio) in &regressions {
+        println!("{puzzle:<8} {part}  regression: {ratio:.1}x slower than the baseline");
+    }
+
+    if baseline.is_none() || save {
+        save_baseline(&path, &current)?;
+        println!("{puzzle:<8} baseline saved to {}", path.display());
+    }
+
+    Ok(regressions.len())
+}
@@ -1,46 +1,269 @@
-use std::{fs::write, io::Error};
+use crate::runner::bin_name;
+use aoc::{
+    client::get_articles,
+    config::Config,
+    description::{find_example_input, find_expected_answer, find_title},
+    puzzle::PuzzleId,
+};
+use std::{
+    env, fs,
+    io::{Error, ErrorKind},
+    path::PathBuf,
+};
 
-pub fn init_template(year: u32, day: u32) -> Result<(), Error> {
-    let filename = format!("./src/bin/{}_{:0>2}.rs", year, day);
+/// inserted for `{{bench}}`, so `aoc bench` can measure the solution
+const BENCH_STUB: &str = "    if let Some(runs) = aoc::bench::requested_runs() {
+        aoc::bench::run_parts(runs, || part_1(&input), || part_2(&input));
+        return Ok(());
+    }
+";
 
-    let template = format!(
-        "use aoc::client::get_input;
-use std::error::Error;
+/// example input and expected answers found in the puzzle description
+#[derive(Debug, Default)]
+struct Example {
+    title: Option<String>,
+    input: Option<String>,
+    answers: [Option<String>; 2],
+}
+
+fn find_example(PuzzleId { year, day }: PuzzleId) -> Example {
+    let articles = match get_articles(year, day) {
+        Ok(articles) => articles,
+        Err(error) => {
+            println!("Could not load puzzle description: {error}");
+            return Example::default();
+        }
+    };
 
-fn main() -> Result<(), Box<dyn Error>> {{
-    let input = get_input({year}, {day})?;
-    println!(\"solution part 1: {{}}\", part_1(&input)?);
-    println!(\"solution part 2: {{}}\", part_2(&input)?);
+    Example {
+        title: articles.first().and_then(|a| find_title(a)),
+        input: articles.first().and_then(|a| find_example_input(a)),
+        answers: [
+            articles.first().and_then(|a| find_expected_answer(a)),
+            articles.get(1).and_then(|a| find_expected_answer(a)),
+        ],
+    }
+}
+
+/// escape a string so it can be used inside of a string literal
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
 
-    Ok(())
-}}
+/// replace the `{{name}}` placeholders of a template
+///
+/// Lines that only contain a placeholder without value are left out.
+fn render(template: &str, values: &[(&str, String)]) -> String {
+    template
+        .lines()
+        .filter_map(|line| {
+            let mut line = line.to_string();
 
-fn part_1(input: &str) -> Result<String, Box<dyn Error>> {{
-    unimplemented!();
-}}
+            for (name, value) in values {
+                let placeholder = format!("{{{{{name}}}}}");
 
-fn part_2(input: &str) -> Result<String, Box<dyn Error>> {{
-    unimplemented!();
-}}
+                if value.is_empty() && line.trim() == placeholder {
+                    return None;
+                }
+                line = line.replace(&placeholder, value);
+            }
+
+            Some(line + "\n")
+        })
+        .collect()
+}
+
+/// how `aoc init` creates a solution
+#[derive(Debug, Default)]
+pub struct InitOptions {
+    /// overwrite an existing solution
+    pub force: bool,
+    /// name of a template in `templates` or path of a template file
+    pub template: Option<String>,
+    /// write the example input to `src/bin/{year}_{dd}.example.txt`
+    pub example_file: bool,
+    /// leave out the benchmark hook for `{{bench}}`, `aoc bench` can not measure the solution then
+    pub no_bench: bool,
+}
+
+impl InitOptions {
+    /// template set with `--template`, `AOC_TEMPLATE` or `template` in the config file
+    fn template_path(&self) -> PathBuf {
+        let template = self
+            .template
+            .clone()
+            .or_else(|| env::var("AOC_TEMPLATE").ok())
+            .or_else(|| {
+                let config = Config::load().unwrap_or_default();
+                config.get(None, "template").map(str::to_string)
+            })
+            .unwrap_or_else(|| String::from("solution"));
+
+        if template.contains('/') || template.ends_with(".rs") {
+            PathBuf::from(template)
+        } else {
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("templates")
+                .join(format!("{template}.rs"))
+        }
+    }
+
+    /// refuse to overwrite an existing solution without `force`
+    pub fn check(&self, puzzle: PuzzleId) -> Result<(), Error> {
+        let path = solution_path(puzzle, "rs");
+
+        if path.exists() && !self.force {
+            return Err(Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} exists, use --force to overwrite it", path.display()),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// values of the placeholders, the `todo_` ones note what was not found in the description
+fn values(
+    PuzzleId { year, day }: PuzzleId,
+    example: &Example,
+    options: &InitOptions,
+) -> Vec<(&'static str, String)> {
+    let optional = |value: &Option<String>| value.as_deref().map(escape).unwrap_or_default();
+    let todo = |value: &Option<String>, what: &str| match value {
+        Some(_) => String::new(),
+        None => format!("// TODO {what} not found in puzzle description"),
+    };
+    let [answer_1, answer_2] = &example.answers;
+    let bench = if options.no_bench { "" } else { BENCH_STUB };
+
+    vec![
+        ("year", year.to_string()),
+        ("day", day.to_string()),
+        ("title", example.title.clone().unwrap_or_default()),
+        ("example", optional(&example.input)),
+        ("answer_1", optional(answer_1)),
+        ("answer_2", optional(answer_2)),
+        ("todo_example", todo(&example.input, "example input")),
+        ("todo_answer_1", todo(answer_1, "expected answer")),
+        ("todo_answer_2", todo(answer_2, "expected answer")),
+        ("bench", bench.to_string()),
+    ]
+}
+
+fn solution_path(puzzle: PuzzleId, extension: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/bin")
+        .join(format!("{}.{extension}", bin_name(puzzle)))
+}
+
+pub fn init_template(puzzle: PuzzleId, options: &InitOptions) -> Result<(), Error> {
+    options.check(puzzle)?;
+
+    let template_path = options.template_path();
+    let template = fs::read_to_string(&template_path).map_err(|error| {
+        Error::new(
+            error.kind(),
+            format!(
+                "could not read template {}: {error}",
+                template_path.display()
+            ),
+        )
+    })?;
+
+    let example = find_example(puzzle);
+    if example.input.is_none() {
+        println!("example input not found in puzzle description");
+    }
+    for (part, answer) in (1..=2).zip(&example.answers) {
+        if answer.is_none() {
+            println!("expected answer of part {part} not found in puzzle description");
+        }
+    }
+
+    let solution = render(&template, &values(puzzle, &example, options));
+
+    if let (true, Some(input)) = (options.example_file, &example.input) {
+        fs::write(solution_path(puzzle, "example.txt"), input)?;
+    }
+
+    fs::write(solution_path(puzzle, "rs"), solution)
+}
 
 #[cfg(test)]
-mod tests {{
+mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = \"\";
+    #[test]
+    fn test_render() {
+        let template =
+            "// {{year}} day {{day}}\nfn main() {\n{{bench}}\n    run(\"{{example}}\");\n}\n";
+
+        assert_eq!(
+            render(
+                template,
+                &[
+                    ("year", String::from("2022")),
+                    ("day", String::from("17")),
+                    ("example", escape("a \"b\"")),
+                    ("bench", String::new()),
+                ]
+            ),
+            "// 2022 day 17\nfn main() {\n    run(\"a \\\"b\\\"\");\n}\n"
+        );
+        assert_eq!(
+            render("{{bench}}\n", &[("bench", String::from("    bench();\n"))]),
+            "    bench();\n\n"
+        );
+    }
 
     #[test]
-    fn test_part_1() {{
-        assert_eq!(part_1(TEST_INPUT).unwrap(), \"\")
-    }}
+    fn test_values() {
+        let template = "    {{todo_example}}\n    const TEST_INPUT: &str = \"{{example}}\";\n";
+        let puzzle = PuzzleId::new(2022, 17).unwrap();
+        let options = InitOptions::default();
+
+        assert_eq!(
+            render(template, &values(puzzle, &Example::default(), &options)),
+            "    // TODO example input not found in puzzle description\n    const TEST_INPUT: &str = \"\";\n"
+        );
+
+        let example = Example {
+            input: Some(String::from(">>><<><>")),
+            ..Example::default()
+        };
+        assert_eq!(
+            render(template, &values(puzzle, &example, &options)),
+            "    const TEST_INPUT: &str = \">>><<><>\";\n"
+        );
+    }
 
     #[test]
-    fn test_part_2() {{
-        assert_eq!(part_2(TEST_INPUT).unwrap(), \"\")
-    }}
-}}
-"
-    );
-
-    write(filename, template)
+    fn test_bundled_templates() {
+        for name in ["solution", "shared_parse"] {
+            let options = InitOptions {
+                template: Some(String::from(name)),
+                ..InitOptions::default()
+            };
+            let template = fs::read_to_string(options.template_path()).unwrap();
+
+            for placeholder in [
+                "{{year}}",
+                "{{day}}",
+                "{{title}}",
+                "{{example}}",
+                "{{todo_example}}",
+                "{{todo_answer_1}}",
+                "{{todo_answer_2}}",
+                "{{bench}}",
+            ] {
+                assert!(template.contains(placeholder), "{name} lacks {placeholder}");
+            }
+
+            // the registry of `build.rs` calls the parts from the aoc binary
+            for part in ["pub fn part_1(", "pub fn part_2("] {
+                assert!(template.contains(part), "{name} lacks {part}");
+            }
+        }
+    }
 }
@@ -0,0 +1,91 @@
+use aoc::{leaderboard::Leaderboard, puzzle::PuzzleId};
+use chrono::Duration;
+
+/// format a duration as hours, minutes and seconds
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+
+    format!(
+        "{}:{:0>2}:{:0>2}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// print the ranking with stars per day, followed by the star times of one day
+pub fn print_leaderboard(leaderboard: &Leaderboard, puzzle: PuzzleId) {
+    let ranking = leaderboard.ranking();
+    let name_width = ranking
+        .iter()
+        .map(|member| member.display_name().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{:>3}  {:<name_width$}  {:>5}  {:>5}           1111111111222222",
+        "",
+        "",
+        "",
+        "",
+        name_width = name_width
+    );
+    println!(
+        "{:>3}  {:<name_width$}  {:>5}  {:>5}  1234567890123456789012345",
+        "#", "name", "score", "stars"
+    );
+
+    for (rank, member) in ranking.iter().enumerate() {
+        let stars: String = (1..=25)
+            .map(|day| match member.stars_on(day) {
+                2 => '*',
+                1 => '.',
+                _ => ' ',
+            })
+            .collect();
+
+        println!(
+            "{:>3}  {:<name_width$}  {:>5}  {:>5}  {stars}",
+            rank + 1,
+            member.display_name(),
+            member.local_score,
+            member.stars
+        );
+    }
+
+    let day = puzzle.day;
+    println!("\nday {day}, time from unlock");
+    println!(
+        "{:<name_width$}  {:>10}  {:>10}",
+        "name", "part 1", "part 2"
+    );
+
+    for member in ranking.iter().filter(|member| member.stars_on(day) > 0) {
+        let times: Vec<String> = (1..=2)
+            .map(|part| {
+                member
+                    .time_to_star(puzzle, part)
+                    .map_or(String::from("-"), format_duration)
+            })
+            .collect();
+
+        println!(
+            "{:<name_width$}  {:>10}  {:>10}",
+            member.display_name(),
+            times[0],
+            times[1]
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::seconds(59)), "0:00:59");
+        assert_eq!(format_duration(Duration::minutes(65)), "1:05:00");
+        assert_eq!(format_duration(Duration::hours(50)), "50:00:00");
+    }
+}
@@ -1,8 +1,35 @@
-use chrono::{Datelike, Utc};
+use aoc::{
+    client::{
+        cache_root, check_auth, clear_cache, encrypt_input_cache, get_cache_index, get_calendar,
+        get_description, get_history, get_input, get_leaderboard, harvest_answers, prefetch_year,
+        verify_cache, ClientError, FetchStatus,
+    },
+    config::Config,
+    description::render,
+    encryption::EncryptionError,
+    profile::Profile,
+    puzzle::{PuzzleId, PuzzleIdError},
+    unlock::{format_countdown, wait_for_unlock, Clock, SystemClock},
+};
+use chrono::{DateTime, Duration, Utc};
 use clap::{Parser, Subcommand};
-use std::{error::Error, process::Command};
+use std::{
+    env,
+    error::Error,
+    io::{self, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
 
+mod all;
+mod bench;
 mod init;
+mod leaderboard;
+mod runner;
+mod solutions;
+mod solve;
+mod submit;
+mod watch;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -10,45 +37,547 @@ struct Args {
     #[command(subcommand)]
     command: Commands,
 
-    day: Option<u32>,
+    /// Profile whose session and inputs are used
+    #[arg(long, global = true, value_parser = parse_profile)]
+    profile: Option<Profile>,
+}
+
+fn parse_profile(name: &str) -> Result<Profile, String> {
+    Profile::new(name)
+}
+
+// puzzle a subcommand works on, not a doc comment as clap would show it as their about
+#[derive(clap::Args, Debug)]
+struct PuzzleArgs {
+    /// Puzzle as `17`, `2022/17` or `2022-17`, defaults to the latest unlocked puzzle
+    puzzle: Option<String>,
 
+    /// Year of the puzzle, if only a day is given
     year: Option<u32>,
 }
 
+impl PuzzleArgs {
+    /// the selected puzzle, it does not have to be unlocked yet
+    fn selected(&self, now: DateTime<Utc>) -> Result<PuzzleId, PuzzleIdError> {
+        select_puzzle(self.puzzle.as_deref(), self.year, now)
+    }
+
+    /// puzzle that is waited for, it can be in the future, without a puzzle it is the next one
+    fn upcoming(&self, now: DateTime<Utc>) -> Result<PuzzleId, PuzzleIdError> {
+        match self.puzzle {
+            Some(_) => self.selected(now),
+            None => Ok(PuzzleId::next(now)),
+        }
+    }
+}
+
 #[derive(Debug, Subcommand)]
 enum Commands {
     #[command()]
-    Init,
+    Init {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Wait until the puzzle unlocks and fetch its input
+        #[arg(long)]
+        wait: bool,
+        /// Overwrite an existing solution
+        #[arg(long)]
+        force: bool,
+        /// Name of a template in `templates` or path of a template file
+        #[arg(long)]
+        template: Option<String>,
+        /// Also write the example input next to the solution
+        #[arg(long)]
+        example_file: bool,
+        /// Leave out the hook that lets `aoc bench` measure the solution
+        #[arg(long)]
+        no_bench: bool,
+    },
+    #[command()]
+    Solve {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Wait until the puzzle unlocks and fetch its input
+        #[arg(long)]
+        wait: bool,
+        /// Only run this part
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+        /// Read the input from this file instead of the cache, `-` reads stdin
+        #[arg(long, conflicts_with = "example")]
+        input: Option<PathBuf>,
+        /// Use the example input of the puzzle description
+        #[arg(long)]
+        example: bool,
+        /// Rebuild, test and solve again whenever the solution or the library changes
+        #[arg(long)]
+        watch: bool,
+    },
+    /// Test and run the solution, then submit its answer
+    #[command()]
+    Submit {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Part to submit, the first part without accepted answer by default
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+        part: Option<u32>,
+    },
+    /// Measure the solutions and compare them to the saved baselines, all of them without a puzzle
+    #[command()]
+    Bench {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+        /// Number of measured runs of each part
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+        /// Slowdown of the median in percent that counts as regression
+        #[arg(long, default_value_t = 20.0)]
+        threshold: f64,
+        /// Replace the saved baselines with the new measurements
+        #[arg(long)]
+        save: bool,
+    },
+    /// Run all implemented solutions and check their answers against the accepted ones
     #[command()]
-    Solve,
+    All {
+        /// Only run the solutions of this year
+        #[arg(long)]
+        year: Option<u32>,
+    },
+    /// Print the puzzle description
+    #[command()]
+    Read {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+    },
+    /// Show the answers submitted for a puzzle
+    #[command()]
+    History {
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+    },
+    /// Show the stars of a year and which days are implemented
+    #[command()]
+    Calendar { year: Option<u32> },
+    /// Download the inputs and descriptions of all unlocked days of a year
+    #[command()]
+    Fetch {
+        #[arg(long)]
+        year: Option<u32>,
+    },
+    /// Collect the accepted answers of all solved puzzles of a year
+    #[command()]
+    Answers { year: Option<u32> },
+    /// Show a private leaderboard with the star times of the selected day
+    #[command()]
+    Leaderboard {
+        id: u64,
+        #[command(flatten)]
+        puzzle: PuzzleArgs,
+    },
+    /// Manage the session
+    #[command(subcommand)]
+    Auth(AuthCommands),
+    /// Manage the input cache
+    #[command(subcommand)]
+    Cache(CacheCommands),
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args = Args::parse();
+#[derive(Debug, Subcommand)]
+enum AuthCommands {
+    /// Check that the session is valid and show the user it belongs to
+    #[command()]
+    Check,
+}
 
-    let datetime = Utc::now();
-    let day = args.day.unwrap_or_else(|| datetime.day());
-    let year = args.year.unwrap_or_else(|| datetime.year() as u32);
+#[derive(Debug, Subcommand)]
+enum CacheCommands {
+    /// Show the downloaded files, when they were fetched and for which profile
+    #[command()]
+    List,
+    /// Check that the downloaded files exist and are valid
+    #[command()]
+    Verify,
+    /// Remove the downloaded files of the profile, the answer history is kept
+    #[command()]
+    Clear {
+        /// Remove the files of all profiles
+        #[arg(long)]
+        all: bool,
+    },
+    /// Encrypt the plaintext inputs of the cache, inputs elsewhere are not decrypted when read
+    #[command()]
+    Encrypt,
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            match error.downcast_ref::<ClientError>() {
+                Some(error) => eprintln!("{}", describe_client_error(error)),
+                None => eprintln!("error: {error}"),
+            }
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// explain what to do about an error of the aoc client
+fn describe_client_error(error: &ClientError) -> String {
+    match error {
+        ClientError::MissingSession(variable) => format!(
+            "no session: set {variable} to the session cookie of adventofcode.com or add it to {}",
+            Config::path().map_or(String::from("the config file"), |path| path
+                .display()
+                .to_string())
+        ),
+        ClientError::SessionExpired => format!(
+            "session expired: refresh {}",
+            Profile::active().unwrap_or_default().session_variable()
+        ),
+        ClientError::NotUnlocked => "puzzle is not unlocked yet: try again later".into(),
+        ClientError::NotFound => "puzzle not found: check day and year".into(),
+        ClientError::Status(status) if error.is_transient() => {
+            format!("adventofcode.com is not available ({status}): try again later")
+        }
+        ClientError::Status(status) => format!("request rejected with status {status}"),
+        ClientError::Throttled(wait) => format!(
+            "request throttled: try again in {}s or set AOC_THROTTLE=wait",
+            wait.as_secs() + 1
+        ),
+        ClientError::Rejected(rejection) => format!("answer not submitted: {rejection}"),
+        ClientError::UnexpectedResponse(message) => {
+            format!("unexpected response ({message}): the site layout may have changed")
+        }
+        ClientError::Encryption(EncryptionError::MissingKey) => {
+            "cached input is encrypted: set AOC_CACHE_PASSPHRASE or AOC_CACHE_KEY_FILE".into()
+        }
+        ClientError::Encryption(error) => format!("could not decrypt the cached input: {error}"),
+        ClientError::InvalidProfile(message) => format!("{message}: check AOC_PROFILE"),
+        ClientError::InvalidBaseUrl(url) => {
+            format!("invalid server url {url:?}: check AOC_BASE_URL or unset it")
+        }
+        ClientError::Http(error) => format!("request failed: check your connection ({error})"),
+        ClientError::Io(error) => format!("could not access the input cache: {error}"),
+    }
+}
+
+/// puzzle selected with the `puzzle` and `year` arguments, it does not have to be unlocked yet
+fn select_puzzle(
+    puzzle: Option<&str>,
+    year: Option<u32>,
+    now: DateTime<Utc>,
+) -> Result<PuzzleId, PuzzleIdError> {
+    match (puzzle, year) {
+        (Some(day), Some(year)) => PuzzleId::parse_at(&format!("{year}/{day}"), now),
+        (Some(puzzle), None) => PuzzleId::parse_at(puzzle, now),
+        (None, _) => Ok(PuzzleId::latest(now)),
+    }
+}
+
+/// show a countdown until the puzzle unlocks, then fetch the input into the cache
+fn wait_and_fetch(puzzle: PuzzleId, clock: &impl Clock) -> Result<(), ClientError> {
+    let PuzzleId { year, day } = puzzle;
+
+    wait_for_unlock(puzzle, clock, |remaining| {
+        print!("\r{puzzle} unlocks in {}  ", format_countdown(remaining));
+        io::stdout().flush().ok();
+    });
+    println!("\r{puzzle} is unlocked{:20}", "");
+
+    // the server clock may be slightly behind, so retry a few times before giving up
+    for _ in 0..5 {
+        match get_input(year, day) {
+            Err(ClientError::NotUnlocked) => clock.sleep(Duration::seconds(2)),
+            result => return result.map(|_| ()),
+        }
+    }
+
+    get_input(year, day).map(|_| ())
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    // passed on through the environment, so solutions started with cargo use it as well
+    if let Some(profile) = &args.profile {
+        env::set_var("AOC_PROFILE", profile.name());
+    }
+
+    let now = Utc::now();
+    // year of the latest unlocked puzzle, for commands that work on a whole year
+    let latest_year = PuzzleId::latest(now).year;
 
     match args.command {
-        Commands::Init => {
-            init::init_template(year, day)?;
+        Commands::Init {
+            puzzle,
+            wait,
+            force,
+            template,
+            example_file,
+            no_bench,
+        } => {
+            let options = init::InitOptions {
+                force,
+                template,
+                example_file,
+                no_bench,
+            };
+
+            let puzzle = if wait {
+                let upcoming = puzzle.upcoming(now)?;
+                // fail before waiting instead of after the unlock
+                options.check(upcoming)?;
+                wait_and_fetch(upcoming, &SystemClock)?;
+                upcoming
+            } else {
+                puzzle.selected(now)?.unlocked(now)?
+            };
+
+            init::init_template(puzzle, &options)?;
+        }
+        Commands::Solve {
+            puzzle,
+            wait,
+            part,
+            input,
+            example,
+            watch,
+        } => {
+            let puzzle = if wait {
+                let upcoming = puzzle.upcoming(now)?;
+                wait_and_fetch(upcoming, &SystemClock)?;
+                upcoming
+            } else {
+                puzzle.selected(now)?.unlocked(now)?
+            };
+
+            let source = solve::InputSource::new(input, example);
+
+            if watch {
+                if source == solve::InputSource::Stdin {
+                    return Err("stdin can only be read once, use --input with a file".into());
+                }
+                watch::watch(puzzle, &solve::solve_args(puzzle, &source, part));
+            }
+
+            solve::solve(puzzle, &source, part)?;
+        }
+        Commands::Submit { puzzle, part } => {
+            submit::submit_puzzle(puzzle.selected(now)?.unlocked(now)?, part)?;
+        }
+        Commands::Bench {
+            puzzle,
+            runs,
+            threshold,
+            save,
+        } => {
+            let puzzles = match puzzle.puzzle {
+                Some(_) => vec![puzzle.selected(now)?],
+                None => runner::implemented_puzzles(),
+            };
+
+            let mut failed = 0;
+            let mut regressions = 0;
+            for puzzle in puzzles {
+                match bench::bench_puzzle(puzzle, runs, threshold / 100.0, save) {
+                    Ok(count) => regressions += count,
+                    Err(error) => {
+                        println!("{puzzle:<8} {error}");
+                        failed += 1;
+                    }
+                }
+            }
+
+            if regressions > 0 || failed > 0 {
+                return Err(format!("{regressions} regressions, {failed} failed solutions").into());
+            }
+        }
+        Commands::All { year: all_year } => {
+            let puzzles = runner::implemented_puzzles()
+                .into_iter()
+                .filter(|puzzle| all_year.is_none_or(|year| puzzle.year == year))
+                .collect::<Vec<_>>();
+
+            // an empty selection must not pass as a successful check
+            if puzzles.is_empty() {
+                return Err(match all_year {
+                    Some(year) => format!("no solutions of {year} in src/bin"),
+                    None => String::from("no solutions in src/bin"),
+                }
+                .into());
+            }
+
+            let summary = all::run_all(&puzzles)?;
+            if summary.failed > 0 {
+                return Err(format!("{} answers do not match", summary.failed).into());
+            }
+        }
+        Commands::Read { puzzle } => {
+            let PuzzleId { year, day } = puzzle.selected(now)?.unlocked(now)?;
+
+            let width = env::var("COLUMNS")
+                .ok()
+                .and_then(|columns| columns.parse().ok())
+                .unwrap_or(80);
+
+            print!("{}", render(&get_description(year, day)?, width));
+        }
+        Commands::History { puzzle } => {
+            let PuzzleId { year, day } = puzzle.selected(now)?;
+            let history = get_history(year, day)?;
+
+            if history.submissions.is_empty() {
+                println!("no answers submitted for {year}-{day}");
+            }
+
+            for submission in history.submissions {
+                println!(
+                    "{}  part {}  {:<16} {}",
+                    submission.time.format("%Y-%m-%d %H:%M:%S"),
+                    submission.part,
+                    submission.verdict,
+                    submission.answer
+                );
+            }
+        }
+        Commands::Calendar {
+            year: calendar_year,
+        } => {
+            let year = calendar_year.unwrap_or(latest_year);
+            let calendar = get_calendar(year)?;
+            let implemented_puzzles = runner::implemented_puzzles();
+
+            println!("{year}: {} stars", calendar.total());
+            for (day, stars) in (1..).zip(&calendar.stars) {
+                let implemented = implemented_puzzles.contains(&PuzzleId { year, day });
+
+                let note = match (stars, implemented) {
+                    (Some(1..), false) => "solved online, missing implementation",
+                    (Some(0) | None, true) => "implemented, not solved online",
+                    _ => "",
+                };
+
+                println!(
+                    "{day:>2}  {:<2}  {}  {note}",
+                    "*".repeat(stars.unwrap_or(0) as usize),
+                    if implemented { "rs" } else { "  " },
+                );
+            }
+        }
+        Commands::Fetch { year: fetch_year } => {
+            let year = fetch_year.unwrap_or(latest_year);
+            let fetches = prefetch_year(year)?;
+
+            println!("{year}:");
+            for fetch in &fetches {
+                println!(
+                    "{:>2}  input {:<10} description {}",
+                    fetch.day, fetch.input, fetch.description
+                );
+            }
+
+            let failed = fetches
+                .iter()
+                .filter(|fetch| {
+                    matches!(fetch.input, FetchStatus::Failed(_))
+                        || matches!(fetch.description, FetchStatus::Failed(_))
+                })
+                .count();
+            if failed > 0 {
+                return Err(
+                    format!("{failed} of {} days could not be fetched", fetches.len()).into(),
+                );
+            }
+        }
+        Commands::Answers { year: answers_year } => {
+            let year = answers_year.unwrap_or(latest_year);
+            let answers = harvest_answers(year)?;
+
+            println!("{year}: {} answers", answers.len());
+            for (day, part, answer) in answers.iter() {
+                println!("{day:>2}  part {part}  {answer}");
+            }
+        }
+        Commands::Leaderboard { id, puzzle } => {
+            let puzzle = puzzle.selected(now)?;
+            leaderboard::print_leaderboard(&get_leaderboard(puzzle.year, id)?, puzzle);
+        }
+        Commands::Cache(CacheCommands::List) => {
+            let index = get_cache_index()?;
+
+            println!("{}: {} files", cache_root().display(), index.entries.len());
+            for (file, entry) in &index.entries {
+                println!(
+                    "{}  {:<10} {}",
+                    entry.fetched.format("%Y-%m-%d %H:%M:%S"),
+                    entry.profile,
+                    file.display()
+                );
+            }
+        }
+        Commands::Cache(CacheCommands::Verify) => {
+            let problems = verify_cache()?;
+
+            for (file, problem) in &problems {
+                println!("{}: {problem}", file.display());
+            }
+            if !problems.is_empty() {
+                return Err(format!("{} cached files have problems", problems.len()).into());
+            }
+            println!("all cached files are valid");
+        }
+        Commands::Cache(CacheCommands::Clear { all }) => {
+            let removed = clear_cache(all)?;
+
+            println!(
+                "{} files removed from {}",
+                removed.len(),
+                cache_root().display()
+            );
+        }
+        Commands::Cache(CacheCommands::Encrypt) => {
+            let encrypted = encrypt_input_cache()?;
+
+            for path in &encrypted {
+                println!("encrypted {}", path.display());
+            }
+            println!("{} inputs encrypted", encrypted.len());
         }
-        Commands::Solve => {
-            println!("solve {year} {day}");
-            let output = Command::new("cargo")
-                .args([
-                    "run",
-                    "--release",
-                    "--bin",
-                    format!("{year}_{day:0>2}").as_str(),
-                ])
-                .output()?;
+        Commands::Auth(AuthCommands::Check) => {
+            let profile = Profile::active()?;
+            let auth = check_auth()?;
 
-            println!("{}", String::from_utf8(output.stderr).unwrap());
-            println!("{}", String::from_utf8(output.stdout).unwrap());
+            println!("profile {}: logged in as {}", profile.name(), auth.user);
+            println!(
+                "session first used {}, expires around {}",
+                auth.first_seen.format("%Y-%m-%d"),
+                auth.expires.format("%Y-%m-%d")
+            );
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_select_puzzle() {
+        let now = Utc.with_ymd_and_hms(2022, 12, 17, 4, 0, 0).unwrap();
+
+        assert_eq!(select_puzzle(None, None, now), PuzzleId::new(2022, 16));
+        assert_eq!(select_puzzle(Some("3"), None, now), PuzzleId::new(2022, 3));
+        assert_eq!(
+            select_puzzle(Some("3"), Some(2021), now),
+            PuzzleId::new(2021, 3)
+        );
+        assert_eq!(
+            select_puzzle(Some("2021/3"), None, now),
+            PuzzleId::new(2021, 3)
+        );
+        assert!(select_puzzle(Some("2021/3"), Some(2021), now).is_err());
+    }
+}
@@ -0,0 +1,163 @@
+use aoc::puzzle::PuzzleId;
+use regex::Regex;
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::PathBuf,
+    process::Command,
+    time::{Duration, Instant},
+};
+
+/// puzzles with a solution in `src/bin`
+pub fn implemented_puzzles() -> Vec<PuzzleId> {
+    let folder = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/bin");
+
+    let mut puzzles = fs::read_dir(folder)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let (year, day) = name.strip_suffix(".rs")?.split_once('_')?;
+
+            PuzzleId::new(year.parse().ok()?, day.parse().ok()?).ok()
+        })
+        .collect::<Vec<_>>();
+
+    puzzles.sort();
+    puzzles
+}
+
+pub fn bin_name(PuzzleId { year, day }: PuzzleId) -> String {
+    format!("{year}_{day:0>2}")
+}
+
+/// build all solutions in release mode
+pub fn build_solutions() -> Result<(), Box<dyn Error>> {
+    let status = Command::new("cargo")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--release", "--bins"])
+        .status()?;
+
+    if !status.success() {
+        return Err("solutions could not be built".into());
+    }
+
+    Ok(())
+}
+
+/// build the solution of a puzzle in release mode
+pub fn build_solution(puzzle: PuzzleId) -> Result<(), Box<dyn Error>> {
+    build_release(&bin_name(puzzle))
+}
+
+/// build a binary of the crate in release mode
+pub fn build_release(bin: &str) -> Result<(), Box<dyn Error>> {
+    let status = Command::new("cargo")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--release", "--bin", bin])
+        .status()?;
+
+    if !status.success() {
+        return Err(format!("{bin} could not be built").into());
+    }
+
+    Ok(())
+}
+
+/// run the unit tests of a solution, returns whether they passed
+pub fn test_solution(puzzle: PuzzleId) -> Result<bool, Box<dyn Error>> {
+    let status = Command::new("cargo")
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["test", "--release", "--bin", &bin_name(puzzle)])
+        .status()?;
+
+    Ok(status.success())
+}
+
+/// path of a binary built in release mode
+pub fn release_binary(bin: &str) -> PathBuf {
+    let target = env::var_os("CARGO_TARGET_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("target"));
+
+    target.join("release").join(bin)
+}
+
+/// command running the built solution of a puzzle
+pub fn solution_command(puzzle: PuzzleId) -> Command {
+    let mut command = Command::new(release_binary(&bin_name(puzzle)));
+    command.current_dir(env!("CARGO_MANIFEST_DIR"));
+    command
+}
+
+/// output of a solution run
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SolutionRun {
+    /// answers of part 1 and 2
+    pub answers: [Option<String>; 2],
+    pub elapsed: Duration,
+}
+
+/// run a built solution and collect its answers
+pub fn run_solution(puzzle: PuzzleId) -> Result<SolutionRun, Box<dyn Error>> {
+    let start = Instant::now();
+    let output = solution_command(puzzle).output()?;
+    let elapsed = start.elapsed();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let message = stderr
+            .lines()
+            .rfind(|line| !line.trim().is_empty())
+            .unwrap_or("no output");
+
+        return Err(format!("solution failed: {message}").into());
+    }
+
+    Ok(SolutionRun {
+        answers: parse_answers(&String::from_utf8_lossy(&output.stdout)),
+        elapsed,
+    })
+}
+
+/// find the `solution part N: answer` lines printed by a solution
+pub fn parse_answers(stdout: &str) -> [Option<String>; 2] {
+    let answer_matcher = Regex::new(r"(?m)^solution part ([12]): (.*)$").unwrap();
+
+    let mut answers = [None, None];
+    for captures in answer_matcher.captures_iter(stdout) {
+        let part = captures[1].parse::<usize>().unwrap();
+        answers[part - 1] = Some(captures[2].trim().to_string());
+    }
+
+    answers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_implemented_puzzles() {
+        let puzzles = implemented_puzzles();
+
+        assert!(puzzles.contains(&PuzzleId::new(2022, 1).unwrap()));
+        assert!(puzzles.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers(
+                "Downloading input for 2022-1\nsolution part 1: 24000\nsolution part 2: 45000\n"
+            ),
+            [Some("24000".into()), Some("45000".into())]
+        );
+        assert_eq!(
+            parse_answers("solution part 1: CMZ\n"),
+            [Some("CMZ".into()), None]
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use aoc::puzzle::PuzzleId;
+use std::error::Error;
+
+pub type Part = fn(&str) -> Result<String, Box<dyn Error>>;
+
+/// both parts of a day, the registry is generated by `build.rs` from the files in `src/bin`
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub part_1: Part,
+    pub part_2: Part,
+}
+
+impl Solution {
+    pub fn part(&self, part: u32) -> Part {
+        match part {
+            1 => self.part_1,
+            _ => self.part_2,
+        }
+    }
+}
+
+/// answers are returned as string, fallible parts with a result
+// only used by the solutions, which are left out of test builds
+#[cfg_attr(test, allow(dead_code))]
+pub trait IntoAnswer {
+    fn into_answer(self) -> Result<String, Box<dyn Error>>;
+}
+
+impl IntoAnswer for String {
+    fn into_answer(self) -> Result<String, Box<dyn Error>> {
+        Ok(self)
+    }
+}
+
+impl IntoAnswer for Result<String, Box<dyn Error>> {
+    fn into_answer(self) -> Result<String, Box<dyn Error>> {
+        self
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
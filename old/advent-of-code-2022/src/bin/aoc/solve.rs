@@ -0,0 +1,145 @@
+use crate::solutions::{find_solution, Part};
+use aoc::{
+    client::{get_articles, get_input},
+    description::find_example_input,
+    puzzle::PuzzleId,
+};
+use std::{error::Error, ffi::OsString, fs, io, panic, path::PathBuf};
+
+/// where the input of a solve comes from
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum InputSource {
+    /// the personal input, downloaded into the cache
+    Cache,
+    /// the first example of the puzzle description
+    Example,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// source selected with `--input` and `--example`, a path of `-` reads stdin
+    pub fn new(input: Option<PathBuf>, example: bool) -> InputSource {
+        match input {
+            Some(path) if path.as_os_str() == "-" => InputSource::Stdin,
+            Some(path) => InputSource::File(path),
+            None if example => InputSource::Example,
+            None => InputSource::Cache,
+        }
+    }
+
+    pub fn read(&self, PuzzleId { year, day }: PuzzleId) -> Result<String, Box<dyn Error>> {
+        Ok(match self {
+            InputSource::Cache => get_input(year, day)?,
+            InputSource::Example => get_articles(year, day)?
+                .first()
+                .and_then(|article| find_example_input(article))
+                .ok_or("no example input found in the puzzle description")?,
+            InputSource::File(path) => fs::read_to_string(path)?,
+            InputSource::Stdin => io::read_to_string(io::stdin())?,
+        })
+    }
+}
+
+/// arguments of `aoc solve` that solve the puzzle the same way again
+pub fn solve_args(puzzle: PuzzleId, source: &InputSource, part: Option<u32>) -> Vec<OsString> {
+    let mut args = vec![OsString::from(puzzle.to_string())];
+
+    if let Some(part) = part {
+        args.extend(["--part".into(), part.to_string().into()]);
+    }
+
+    match source {
+        InputSource::Cache => {}
+        InputSource::Example => args.push("--example".into()),
+        InputSource::File(path) => args.extend(["--input".into(), path.into()]),
+        InputSource::Stdin => args.extend(["--input".into(), "-".into()]),
+    }
+
+    args
+}
+
+/// run the parts of a solution in-process, all of them if no part is given
+pub fn solve(
+    puzzle: PuzzleId,
+    source: &InputSource,
+    part: Option<u32>,
+) -> Result<(), Box<dyn Error>> {
+    let solution =
+        find_solution(puzzle).ok_or_else(|| format!("no solution for {puzzle} in src/bin"))?;
+    let input = source.read(puzzle)?;
+
+    let parts = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    for part in parts {
+        let answer = run_part(solution.part(part), &input)
+            .map_err(|error| format!("part {part} failed: {error}"))?;
+        println!("solution part {part}: {answer}");
+    }
+
+    Ok(())
+}
+
+/// run a part, a panic like the `unimplemented!()` of a new day is returned as error
+fn run_part(part: Part, input: &str) -> Result<String, Box<dyn Error>> {
+    panic::catch_unwind(|| part(input)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("panicked");
+
+        Err(message.into())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_input_source() {
+        assert_eq!(InputSource::new(None, false), InputSource::Cache);
+        assert_eq!(InputSource::new(None, true), InputSource::Example);
+        assert_eq!(
+            InputSource::new(Some(PathBuf::from("-")), false),
+            InputSource::Stdin
+        );
+        assert_eq!(
+            InputSource::new(Some(PathBuf::from("input.txt")), false),
+            InputSource::File(PathBuf::from("input.txt"))
+        );
+    }
+
+    #[test]
+    fn test_run_part() {
+        assert_eq!(
+            run_part(|input| Ok(input.len().to_string()), "abc").unwrap(),
+            "3"
+        );
+        assert_eq!(
+            run_part(|_| unimplemented!(), "").unwrap_err().to_string(),
+            "not implemented"
+        );
+        assert_eq!(
+            run_part(|input| panic!("bad input {input}"), "x")
+                .unwrap_err()
+                .to_string(),
+            "bad input x"
+        );
+    }
+
+    #[test]
+    fn test_solve_args() {
+        let puzzle = PuzzleId::new(2022, 17).unwrap();
+
+        assert_eq!(solve_args(puzzle, &InputSource::Cache, None), ["2022-17"]);
+        assert_eq!(
+            solve_args(puzzle, &InputSource::File("input.txt".into()), Some(2)),
+            ["2022-17", "--part", "2", "--input", "input.txt"]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::runner::{build_solution, run_solution, test_solution};
+use aoc::{
+    answers::Answers,
+    client::{get_answers, record_answer, submit_answer, Verdict},
+    puzzle::PuzzleId,
+};
+use std::{
+    error::Error,
+    io::{self, BufRead, Write},
+};
+
+/// the first part without accepted answer
+pub fn default_part(answers: &Answers, day: u32) -> u32 {
+    match answers.get(day, 1) {
+        Some(_) => 2,
+        None => 1,
+    }
+}
+
+/// ask a yes/no question, an empty reply counts as yes
+fn confirm(question: &str) -> Result<bool, io::Error> {
+    print!("{question} [Y/n] ");
+    io::stdout().flush()?;
+
+    let mut reply = String::new();
+    io::stdin().lock().read_line(&mut reply)?;
+
+    Ok(matches!(reply.trim(), "" | "y" | "Y" | "yes"))
+}
+
+/// test and run the solution of a puzzle, then submit the answer of one part
+pub fn submit_puzzle(puzzle: PuzzleId, part: Option<u32>) -> Result<(), Box<dyn Error>> {
+    let PuzzleId { year, day } = puzzle;
+    let part = match part {
+        Some(part) => part,
+        None => default_part(&get_answers(year)?, day),
+    };
+
+    if !test_solution(puzzle)? {
+        return Err(format!("unit tests of {puzzle} failed, nothing submitted").into());
+    }
+
+    build_solution(puzzle)?;
+    let run = run_solution(puzzle)?;
+    let answer = run.answers[part as usize - 1]
+        .clone()
+        .ok_or_else(|| format!("solution of {puzzle} printed no answer for part {part}"))?;
+
+    println!("submitting {answer} for {puzzle} part {part}");
+    let verdict = submit_answer(year, day, part, &answer)?;
+
+    match verdict {
+        Verdict::Correct => {
+            println!("correct answer");
+            if confirm(&format!("record {answer} as the accepted answer?"))? {
+                record_answer(year, day, part, &answer)?;
+            }
+        }
+        Verdict::Wrong => println!("wrong answer"),
+        Verdict::TooHigh => println!("wrong answer, it is too high"),
+        Verdict::TooLow => println!("wrong answer, it is too low"),
+        Verdict::AlreadySolved => println!("part {part} is already solved"),
+        Verdict::RateLimited(wait) => {
+            println!("rate limited, try again in {}s", wait.as_secs());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_part() {
+        let answers = "1 1 24000\n2 1 15\n2 2 12\n".parse::<Answers>().unwrap();
+
+        assert_eq!(default_part(&answers, 1), 2);
+        assert_eq!(default_part(&answers, 3), 1);
+    }
+}
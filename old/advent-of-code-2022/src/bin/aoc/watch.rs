@@ -0,0 +1,114 @@
+use crate::runner::{bin_name, build_release, release_binary, test_solution};
+use aoc::puzzle::PuzzleId;
+use std::{
+    ffi::OsString,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// saves in quick succession only trigger one run
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// source file of the day and the modules of the shared library
+pub fn watched_files(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+
+    let mut files = fs::read_dir(&src)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect::<Vec<_>>();
+
+    files.push(src.join("bin").join(format!("{}.rs", bin_name(puzzle))));
+    files.sort();
+    files
+}
+
+/// latest modification of the files, files that can not be read are skipped
+fn last_modified(files: &[PathBuf]) -> Option<SystemTime> {
+    files
+        .iter()
+        .filter_map(|file| fs::metadata(file).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+/// wait until the files were modified after `since` and stopped changing
+fn wait_for_change(files: &[PathBuf], since: Option<SystemTime>) -> Option<SystemTime> {
+    loop {
+        let modified = last_modified(files);
+
+        if modified != since {
+            thread::sleep(DEBOUNCE);
+            if last_modified(files) == modified {
+                return modified;
+            }
+        } else {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// rebuild, then run the unit tests and the solution, build errors are shown and skip the run
+fn run_once(puzzle: PuzzleId, solve_args: &[OsString]) {
+    // clear the screen and move the cursor to the top
+    print!("\x1b[2J\x1b[H");
+    io::stdout().flush().ok();
+
+    if let Err(error) = build_release("aoc") {
+        println!("{error}");
+        return;
+    }
+
+    match test_solution(puzzle) {
+        Ok(true) => {}
+        Ok(false) => println!("unit tests of {puzzle} failed\n"),
+        Err(error) => println!("could not run the unit tests: {error}\n"),
+    }
+
+    let status = Command::new(release_binary("aoc"))
+        .arg("solve")
+        .args(solve_args)
+        .status();
+
+    if let Err(error) = status {
+        println!("could not run the solution: {error}");
+    }
+}
+
+/// solve the puzzle again whenever its source or the library changes, until interrupted
+pub fn watch(puzzle: PuzzleId, solve_args: &[OsString]) -> ! {
+    let files = watched_files(puzzle);
+    let mut last_run = None;
+
+    loop {
+        last_run = wait_for_change(&files, last_run);
+        run_once(puzzle, solve_args);
+        println!("\nwatching {puzzle} for changes, press ctrl-c to stop");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watched_files() {
+        let files = watched_files(PuzzleId::new(2022, 17).unwrap());
+        let names = files
+            .iter()
+            .map(|file| file.file_name().unwrap().to_str().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(names.contains(&"2022_17.rs"));
+        assert!(names.contains(&"point.rs"));
+        assert!(names.contains(&"client.rs"));
+        assert!(!names.contains(&"2022_01.rs"));
+    }
+}
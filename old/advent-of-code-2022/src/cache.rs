@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// when and for which profile a cache entry was downloaded
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IndexEntry {
+    pub fetched: DateTime<Utc>,
+    pub profile: String,
+}
+
+/// index of the files in the cache, with paths relative to the cache folder
+///
+/// The index is stored with one tab-separated line per file: path, profile and the time
+/// it was fetched.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct CacheIndex {
+    pub entries: BTreeMap<PathBuf, IndexEntry>,
+}
+
+impl CacheIndex {
+    /// read the index, a missing file is an empty index
+    pub fn load(path: &Path) -> Result<CacheIndex, io::Error> {
+        let content = match fs::read_to_string(path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            content => content?,
+        };
+
+        let entries = content
+            .lines()
+            .map(|line| {
+                parse_entry(line)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, line.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CacheIndex { entries })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), io::Error> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+
+        let content = self
+            .entries
+            .iter()
+            .map(|(file, entry)| {
+                format!(
+                    "{}\t{}\t{}\n",
+                    file.display(),
+                    entry.profile,
+                    entry.fetched.to_rfc3339()
+                )
+            })
+            .collect::<String>();
+
+        fs::write(path, content)
+    }
+
+    pub fn insert(&mut self, file: PathBuf, profile: &str, fetched: DateTime<Utc>) {
+        self.entries.insert(
+            file,
+            IndexEntry {
+                fetched,
+                profile: profile.to_string(),
+            },
+        );
+    }
+}
+
+fn parse_entry(line: &str) -> Option<(PathBuf, IndexEntry)> {
+    let mut fields = line.splitn(3, '\t');
+
+    let file = PathBuf::from(fields.next()?);
+    let profile = fields.next()?.to_string();
+    let fetched = DateTime::parse_from_rfc3339(fields.next()?)
+        .ok()?
+        .with_timezone(&Utc);
+
+    Some((file, IndexEntry { fetched, profile }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::env;
+
+    #[test]
+    fn test_save_load() {
+        let path = env::temp_dir().join(format!("aoc-index-{}", std::process::id()));
+        let fetched = Utc.with_ymd_and_hms(2022, 12, 1, 5, 0, 3).unwrap();
+
+        let mut index = CacheIndex::default();
+        index.insert(PathBuf::from("2022_01.txt"), "default", fetched);
+        index.insert(PathBuf::from("alice/2022_01.txt"), "alice", fetched);
+        index.save(&path).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "2022_01.txt\tdefault\t2022-12-01T05:00:03+00:00
+alice/2022_01.txt\talice\t2022-12-01T05:00:03+00:00
+"
+        );
+        assert_eq!(CacheIndex::load(&path).unwrap(), index);
+
+        fs::remove_file(&path).unwrap();
+        assert_eq!(CacheIndex::load(&path).unwrap(), CacheIndex::default());
+    }
+}
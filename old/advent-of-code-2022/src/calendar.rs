@@ -0,0 +1,98 @@
+use regex::Regex;
+use std::{fmt, num::ParseIntError, str::FromStr};
+
+/// stars collected for every day of a year
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Calendar {
+    /// stars (0, 1 or 2) of day 1 to 25, `None` if the day is not unlocked
+    pub stars: [Option<u8>; 25],
+}
+
+impl Calendar {
+    /// parse the calendar page of a year
+    pub fn parse(html: &str) -> Calendar {
+        let day_matcher = Regex::new(r#"aria-label="Day (\d+)(, one star|, two stars)?""#).unwrap();
+
+        let mut calendar = Calendar::default();
+
+        for captures in day_matcher.captures_iter(html) {
+            let day = captures[1].parse::<usize>().unwrap();
+            let stars = match captures.get(2).map(|m| m.as_str()) {
+                Some(", two stars") => 2,
+                Some(_) => 1,
+                None => 0,
+            };
+
+            if let Some(entry) = calendar.stars.get_mut(day.wrapping_sub(1)) {
+                *entry = Some(stars);
+            }
+        }
+
+        calendar
+    }
+
+    pub fn total(&self) -> u32 {
+        self.stars.iter().flatten().map(|&stars| stars as u32).sum()
+    }
+}
+
+/// one line per unlocked day with the day and its stars, e.g. `17 2`
+impl fmt::Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, stars) in self.stars.iter().enumerate() {
+            if let Some(stars) = stars {
+                writeln!(f, "{} {stars}", i + 1)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Calendar {
+    type Err = ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut calendar = Calendar::default();
+
+        for line in s.lines() {
+            let (day, stars) = line.split_once(' ').unwrap_or((line, ""));
+            let day = day.parse::<usize>()?;
+            let stars = stars.parse::<u8>()?;
+
+            if let Some(entry) = calendar.stars.get_mut(day.wrapping_sub(1)) {
+                *entry = Some(stars);
+            }
+        }
+
+        Ok(calendar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_HTML: &str = r#"<pre class="calendar">
+<a aria-label="Day 1, two stars" href="/2022/day/1" class="calendar-day1 calendar-verycomplete"> <span class="calendar-day"> 1</span></a>
+<a aria-label="Day 2, one star" href="/2022/day/2" class="calendar-day2 calendar-complete"> <span class="calendar-day"> 2</span></a>
+<a aria-label="Day 3" href="/2022/day/3" class="calendar-day3"> <span class="calendar-day"> 3</span></a>
+<span aria-hidden="true" class="calendar-day4"> <span class="calendar-day"> 4</span></span>
+</pre>"#;
+
+    #[test]
+    fn test_parse() {
+        let calendar = Calendar::parse(TEST_HTML);
+
+        assert_eq!(calendar.stars[..4], [Some(2), Some(1), Some(0), None]);
+        assert_eq!(calendar.total(), 3);
+    }
+
+    #[test]
+    fn test_to_string() {
+        let calendar = Calendar::parse(TEST_HTML);
+
+        assert_eq!(calendar.to_string(), "1 2\n2 1\n3 0\n");
+        assert_eq!(calendar.to_string().parse(), Ok(calendar));
+    }
+}
@@ -1,67 +1,1252 @@
-use reqwest::{blocking::Client, cookie::Jar, Url};
-use std::{env, error::Error, io, path::Path, sync::Arc};
+use crate::{
+    answers::Answers,
+    cache::CacheIndex,
+    calendar::Calendar,
+    config::Config,
+    description::{extract_articles, find_answers, html_to_markdown},
+    encryption::{encrypt_folder, is_encrypted, CacheKey, EncryptionError},
+    history::{History, Rejection, Submission},
+    leaderboard::Leaderboard,
+    profile::Profile,
+    puzzle::{PuzzleId, PuzzleIdError},
+    throttle::{Throttle, ThrottleMode, THROTTLE_INTERVAL},
+};
+use blake2::{Blake2s256, Digest};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use reqwest::{
+    blocking::{Client, RequestBuilder, Response},
+    cookie::Jar,
+    StatusCode, Url,
+};
+use std::{
+    cell::Cell,
+    env,
+    error::Error,
+    fmt, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
 
-const INPUT_CACHE_FOLDER: &str = "./.input/";
+const INPUT_CACHE_FOLDER: &str = ".input";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+/// sessions of adventofcode.com are valid for about a month
+const SESSION_LIFETIME: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+const CALENDAR_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+/// the leaderboard api must not be requested more than once every 15 minutes
+const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
 
-fn get_aoc_client() -> Result<Client, Box<dyn Error>> {
-    let cookie = format!("session={}", env::var("AOC_SESSION")?);
+/// result of an answer submission as reported by adventofcode.com
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    AlreadySolved,
+    RateLimited(Duration),
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+            Verdict::RateLimited(wait) => write!(f, "rate-limited {}", wait.as_secs()),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "already-solved" => Ok(Verdict::AlreadySolved),
+            _ => s
+                .strip_prefix("rate-limited ")
+                .and_then(|seconds| seconds.parse().ok())
+                .map(|seconds| Verdict::RateLimited(Duration::from_secs(seconds)))
+                .ok_or_else(|| format!("unknown verdict {s}")),
+        }
+    }
+}
+
+/// number of days that are downloaded at the same time by `prefetch_year`
+const PREFETCH_THREADS: usize = 4;
+
+const MAX_RETRIES: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// errors returned when talking to adventofcode.com
+#[derive(Debug)]
+pub enum ClientError {
+    /// the environment variable with the session of the active profile is not set
+    MissingSession(String),
+    /// the session cookie is not accepted anymore
+    SessionExpired,
+    /// the puzzle has not been unlocked yet
+    NotUnlocked,
+    /// there is no puzzle for this day
+    NotFound,
+    /// the server responded with an unexpected status code
+    Status(StatusCode),
+    /// the request would violate the throttle, it is allowed after the given time
+    Throttled(Duration),
+    /// the answer is not submitted because of earlier submissions
+    Rejected(Rejection),
+    /// the response does not have the expected content
+    UnexpectedResponse(&'static str),
+    /// a cached input could not be decrypted
+    Encryption(EncryptionError),
+    /// `AOC_PROFILE` is not a valid profile name
+    InvalidProfile(String),
+    /// `AOC_BASE_URL` is not a valid url
+    InvalidBaseUrl(String),
+    Http(reqwest::Error),
+    Io(io::Error),
+}
+
+impl ClientError {
+    /// whether the request might succeed when it is retried
+    pub fn is_transient(&self) -> bool {
+        match self {
+            ClientError::Status(status) => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            ClientError::Http(error) => error.is_timeout() || error.is_connect(),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::MissingSession(variable) => write!(f, "{variable} is not set"),
+            ClientError::SessionExpired => write!(f, "session is not logged in"),
+            ClientError::NotUnlocked => write!(f, "puzzle is not unlocked yet"),
+            ClientError::NotFound => write!(f, "puzzle does not exist"),
+            ClientError::Status(status) => write!(f, "unexpected response status {status}"),
+            ClientError::Throttled(wait) => write!(f, "request throttled for {wait:?}"),
+            ClientError::Rejected(rejection) => write!(f, "answer not submitted: {rejection}"),
+            ClientError::UnexpectedResponse(message) => write!(f, "unexpected response: {message}"),
+            ClientError::Encryption(error) => write!(f, "encrypted cache: {error}"),
+            ClientError::InvalidProfile(message) => write!(f, "AOC_PROFILE: {message}"),
+            ClientError::InvalidBaseUrl(url) => write!(f, "AOC_BASE_URL is not a valid url: {url}"),
+            ClientError::Http(error) => write!(f, "request failed: {error}"),
+            ClientError::Io(error) => write!(f, "cache error: {error}"),
+        }
+    }
+}
+
+impl Error for ClientError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ClientError::Http(error) => Some(error),
+            ClientError::Io(error) => Some(error),
+            ClientError::Rejected(rejection) => Some(rejection),
+            ClientError::Encryption(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(error: reqwest::Error) -> Self {
+        ClientError::Http(error)
+    }
+}
+
+impl From<EncryptionError> for ClientError {
+    fn from(error: EncryptionError) -> Self {
+        ClientError::Encryption(error)
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(error: io::Error) -> Self {
+        ClientError::Io(error)
+    }
+}
 
-    let url = "https://adventofcode.com".parse::<Url>()?;
+/// what happened to a cache entry during a prefetch
+#[derive(Debug)]
+pub enum FetchStatus {
+    Fetched,
+    /// the entry was already cached and complete
+    Skipped,
+    Failed(ClientError),
+}
+
+impl fmt::Display for FetchStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchStatus::Fetched => write!(f, "fetched"),
+            FetchStatus::Skipped => write!(f, "skipped"),
+            FetchStatus::Failed(error) => write!(f, "failed ({error})"),
+        }
+    }
+}
+
+/// result of prefetching a day
+#[derive(Debug)]
+pub struct DayFetch {
+    pub day: u32,
+    pub input: FetchStatus,
+    pub description: FetchStatus,
+}
+
+/// account a session belongs to
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Auth {
+    pub user: String,
+    /// first time the session was successfully used with this tool
+    pub first_seen: DateTime<Utc>,
+    /// estimated end of the session, based on when it was first seen
+    pub expires: DateTime<Utc>,
+}
+
+fn get_session() -> Result<String, ClientError> {
+    let profile = Profile::active()?;
+
+    profile
+        .session()
+        .ok_or_else(|| ClientError::MissingSession(profile.session_variable()))
+}
+
+fn get_aoc_client() -> Result<Client, ClientError> {
+    let cookie = format!("session={}", get_session()?);
+
+    let url = parse_base_url(&base_url())?;
 
     let jar = Arc::new(Jar::default());
     jar.add_cookie_str(&cookie, &url);
 
-    let client = Client::builder().cookie_provider(jar).build()?;
+    let client = Client::builder()
+        .cookie_provider(jar)
+        .user_agent(user_agent(get_contact().as_deref()))
+        .build()?;
 
     Ok(client)
 }
 
-fn get_input_path(year: u32, day: u32) -> String {
-    format!("{INPUT_CACHE_FOLDER}{year}_{day:0>2}.txt")
+/// contact from the `AOC_CONTACT` environment variable or the config file
+fn get_contact() -> Option<String> {
+    env::var("AOC_CONTACT").ok().or_else(|| {
+        Config::load()
+            .ok()?
+            .get(None, "contact")
+            .map(str::to_string)
+    })
+}
+
+/// identify the tool and a contact, as requested by the maintainers of adventofcode.com
+fn user_agent(contact: Option<&str>) -> String {
+    let version = env!("CARGO_PKG_VERSION");
+
+    match contact {
+        Some(contact) => format!("aoc/{version} ({contact})"),
+        None => format!("aoc/{version}"),
+    }
+}
+
+/// url of the aoc server, can be changed with the `AOC_BASE_URL` environment variable
+fn parse_base_url(url: &str) -> Result<Url, ClientError> {
+    url.parse()
+        .map_err(|_| ClientError::InvalidBaseUrl(url.to_string()))
+}
+
+fn base_url() -> String {
+    env::var("AOC_BASE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_else(|_| DEFAULT_BASE_URL.to_string())
+}
+
+/// root of the input cache
+///
+/// This is the `AOC_CACHE_DIR` environment variable if it is set, otherwise `.input` in the
+/// project root. Outside of the project, e.g. for an installed binary, `aoc` in the XDG cache
+/// directory is used.
+pub fn cache_root() -> PathBuf {
+    if let Some(folder) = env::var_os("AOC_CACHE_DIR") {
+        return PathBuf::from(folder);
+    }
+
+    let project = Path::new(env!("CARGO_MANIFEST_DIR"));
+    if project.is_dir() {
+        return project.join(INPUT_CACHE_FOLDER);
+    }
+
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .map_or_else(
+            || PathBuf::from(INPUT_CACHE_FOLDER),
+            |cache| cache.join("aoc"),
+        )
+}
+
+/// cache folder of the active profile
+fn cache_folder() -> Result<PathBuf, ClientError> {
+    Ok(Profile::active()?.cache_folder(&cache_root()))
+}
+
+thread_local! {
+    /// throttle mode of the requests of this thread, instead of the one set with `AOC_THROTTLE`
+    static THROTTLE_MODE: Cell<Option<ThrottleMode>> = const { Cell::new(None) };
+}
+
+fn get_throttle() -> Throttle {
+    let interval = env::var("AOC_THROTTLE_INTERVAL")
+        .ok()
+        .and_then(|seconds| seconds.parse::<f64>().ok())
+        .map_or(THROTTLE_INTERVAL, Duration::from_secs_f64);
+
+    Throttle::new(
+        cache_root().join(".throttle"),
+        interval,
+        THROTTLE_MODE.get().unwrap_or_else(ThrottleMode::from_env),
+    )
+}
+
+/// send a throttled request, transient failures are retried with exponential backoff
+///
+/// Only for idempotent requests, see [`send_once`].
+fn send(endpoint: &str, request: RequestBuilder) -> Result<Response, ClientError> {
+    let throttle = get_throttle();
+    let mut backoff = INITIAL_BACKOFF;
+
+    throttle.acquire(endpoint)?;
+
+    for attempt in 0.. {
+        if attempt > 0 {
+            throttle.acquire_with_mode(endpoint, ThrottleMode::Wait)?;
+        }
+
+        match attempt_request(&request) {
+            Err(error) if error.is_transient() && attempt < MAX_RETRIES => {
+                println!("{error}, retrying in {backoff:?}");
+                thread::sleep(backoff);
+                backoff *= 2;
+            }
+            result => return result,
+        }
+    }
+
+    unreachable!()
+}
+
+/// send a throttled request without retries
+///
+/// A request that timed out may still have been processed, so answers are never sent twice.
+fn send_once(endpoint: &str, request: RequestBuilder) -> Result<Response, ClientError> {
+    get_throttle().acquire(endpoint)?;
+
+    attempt_request(&request)
+}
+
+fn attempt_request(request: &RequestBuilder) -> Result<Response, ClientError> {
+    request
+        .try_clone()
+        .expect("request body is not a stream")
+        .send()
+        .map_err(ClientError::from)
+        .and_then(|res| match res.status() {
+            status if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS => {
+                Err(ClientError::Status(status))
+            }
+            _ => Ok(res),
+        })
+}
+
+/// check that a puzzle exists and is unlocked, before the server is asked for it
+fn check_puzzle(year: u32, day: u32) -> Result<PuzzleId, ClientError> {
+    PuzzleId::new(year, day)
+        .and_then(|puzzle| puzzle.unlocked(Utc::now()))
+        .map_err(|error| match error {
+            PuzzleIdError::NotUnlocked(..) => ClientError::NotUnlocked,
+            _ => ClientError::NotFound,
+        })
+}
+
+fn get_input_path(year: u32, day: u32) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("{year}_{day:0>2}.txt")))
+}
+
+fn get_description_path(year: u32, day: u32) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("{year}_{day:0>2}.md")))
+}
+
+fn get_puzzle_path(year: u32, day: u32) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("{year}_{day:0>2}.html")))
+}
+
+fn get_calendar_path(year: u32) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("{year}.calendar")))
+}
+
+fn get_answers_path(year: u32) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("{year}.answers")))
+}
+
+fn get_leaderboard_path(year: u32, id: u64) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("leaderboard_{year}_{id}.json")))
+}
+
+fn get_history_path(year: u32, day: u32) -> Result<PathBuf, ClientError> {
+    Ok(cache_folder()?.join(format!("{year}_{day:0>2}.log")))
+}
+
+/// read input from the cache, entries that are not valid inputs are removed
+///
+/// Encrypted entries are decrypted with the configured key, they are never removed
+/// when that fails.
+fn get_cached_input(year: u32, day: u32) -> Result<String, ClientError> {
+    let input = read_input_file(&get_input_path(year, day)?)?;
+
+    if let Err(error) = check_input(StatusCode::OK, &input) {
+        println!("Removing invalid cached input for {year}-{day}: {error}");
+        invalidate_input(year, day)?;
+        return Err(error);
+    }
+
+    Ok(input)
+}
+
+/// read a cached input file, decrypting it if necessary
+fn read_input_file(path: &Path) -> Result<String, ClientError> {
+    let mut data = std::fs::read(path)?;
+
+    if is_encrypted(&data) {
+        let key = CacheKey::from_env()?.ok_or(EncryptionError::MissingKey)?;
+        data = key.decrypt(&data)?;
+    }
+
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+/// cache an input, encrypted if a key is configured
+fn write_input_cache(year: u32, day: u32, input: &str) -> Result<(), ClientError> {
+    let path = get_input_path(year, day)?;
+
+    match CacheKey::from_env()? {
+        Some(key) => write_fetched(&path, key.encrypt(input.as_bytes())),
+        None => write_fetched(&path, input),
+    }
+}
+
+/// encrypt the plaintext inputs in the cache of the active profile
+///
+/// Only the cache is decrypted when it is read, so other folders, like the inputs that the
+/// 2021 solutions embed with `include_str!`, must stay plaintext.
+pub fn encrypt_input_cache() -> Result<Vec<PathBuf>, ClientError> {
+    let key = CacheKey::from_env()?.ok_or(EncryptionError::MissingKey)?;
+
+    Ok(encrypt_folder(&cache_folder()?, &key)?)
 }
 
-fn get_cached_input(year: u32, day: u32) -> Result<String, io::Error> {
-    std::fs::read_to_string(get_input_path(year, day))
+/// remove a cached input so it is downloaded again
+pub fn invalidate_input(year: u32, day: u32) -> Result<(), ClientError> {
+    match std::fs::remove_file(get_input_path(year, day)?) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error.into()),
+        _ => Ok(()),
+    }
 }
 
-fn cache_input(year: u32, day: u32, input: &str) -> Result<(), io::Error> {
-    let input_path = Path::new(INPUT_CACHE_FOLDER);
+/// check that a response to an input request contains an actual input
+fn check_input(status: StatusCode, body: &str) -> Result<(), ClientError> {
+    if status == StatusCode::NOT_FOUND {
+        return Err(if body.contains("before it unlocks") {
+            ClientError::NotUnlocked
+        } else {
+            ClientError::NotFound
+        });
+    }
+
+    if body.contains("Please log in") {
+        return Err(ClientError::SessionExpired);
+    }
+
+    if !status.is_success() {
+        return Err(ClientError::Status(status));
+    }
 
-    if !std::path::Path::exists(input_path) {
-        std::fs::create_dir(INPUT_CACHE_FOLDER)?;
+    // inputs can start with `<` as well, like the jet pattern of 2022 day 17
+    let start = body.trim_start().chars().take(9).collect::<String>();
+    let start = start.to_ascii_lowercase();
+    if start.is_empty() || start.starts_with("<!doctype") || start.starts_with("<html") {
+        return Err(ClientError::UnexpectedResponse(
+            "html page instead of puzzle input",
+        ));
     }
 
-    std::fs::write(get_input_path(year, day), input)
+    Ok(())
+}
+
+/// read a cached file if it is younger than `max_age`
+fn read_fresh_cache(path: &Path, max_age: Duration) -> Option<String> {
+    let age = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()?
+        .elapsed()
+        .ok()?;
+
+    if age >= max_age {
+        return None;
+    }
+
+    std::fs::read_to_string(path).ok()
+}
+
+fn write_cache(path: &Path, content: impl AsRef<[u8]>) -> Result<(), io::Error> {
+    if let Some(folder) = path.parent() {
+        std::fs::create_dir_all(folder)?;
+    }
+
+    std::fs::write(path, content)
+}
+
+fn get_index_path() -> PathBuf {
+    cache_root().join(".index")
+}
+
+/// cache a downloaded file and record it in the index
+fn write_fetched(path: &Path, content: impl AsRef<[u8]>) -> Result<(), ClientError> {
+    // the index is rewritten on every update, prefetch threads must not interleave
+    static INDEX_LOCK: Mutex<()> = Mutex::new(());
+
+    write_cache(path, content)?;
+
+    let _guard = INDEX_LOCK.lock().unwrap_or_else(|error| error.into_inner());
+    let root = cache_root();
+    let mut index = CacheIndex::load(&get_index_path())?;
+
+    index.insert(
+        path.strip_prefix(&root).unwrap_or(path).to_path_buf(),
+        Profile::active()?.name(),
+        Utc::now(),
+    );
+
+    Ok(index.save(&get_index_path())?)
+}
+
+/// index of the downloaded files in the cache
+pub fn get_cache_index() -> Result<CacheIndex, ClientError> {
+    Ok(CacheIndex::load(&get_index_path())?)
+}
+
+/// check the entries of the cache index, returns the files with a problem
+///
+/// Files must exist and have the content expected for their type, inputs must be
+/// readable with the configured key.
+pub fn verify_cache() -> Result<Vec<(PathBuf, String)>, ClientError> {
+    let root = cache_root();
+    let mut problems = Vec::new();
+
+    for file in get_cache_index()?.entries.keys() {
+        let path = root.join(file);
+
+        if !path.exists() {
+            problems.push((file.clone(), String::from("file is missing")));
+            continue;
+        }
+
+        let problem = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("txt") => read_input_file(&path)
+                .and_then(|input| check_input(StatusCode::OK, &input))
+                .err()
+                .map(|error| error.to_string()),
+            Some("html") => std::fs::read_to_string(&path)
+                .map_or(true, |html| extract_articles(&html).is_empty())
+                .then(|| String::from("page contains no description")),
+            Some("calendar") => std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|calendar| calendar.parse::<Calendar>().ok())
+                .is_none()
+                .then(|| String::from("calendar is damaged")),
+            Some("answers") => std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|answers| answers.parse::<Answers>().ok())
+                .is_none()
+                .then(|| String::from("answers file is damaged")),
+            Some("json") => std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|json| serde_json::from_str::<Leaderboard>(&json).ok())
+                .is_none()
+                .then(|| String::from("leaderboard is damaged")),
+            _ => None,
+        };
+
+        if let Some(problem) = problem {
+            problems.push((file.clone(), problem));
+        }
+    }
+
+    Ok(problems)
+}
+
+/// remove the downloaded files of the active profile, or of all profiles
+///
+/// The answer history is not downloaded and is kept.
+pub fn clear_cache(all_profiles: bool) -> Result<Vec<PathBuf>, ClientError> {
+    let root = cache_root();
+    let profile = Profile::active()?;
+    let mut index = get_cache_index()?;
+
+    let (removed, kept) = std::mem::take(&mut index.entries)
+        .into_iter()
+        .partition(|(_, entry)| all_profiles || entry.profile == profile.name());
+    index.entries = kept;
+
+    let removed = removed.into_keys().collect::<Vec<PathBuf>>();
+    for file in &removed {
+        match std::fs::remove_file(root.join(file)) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error.into()),
+            _ => (),
+        }
+    }
+
+    index.save(&get_index_path())?;
+
+    Ok(removed)
 }
 
 /// download puzzle input
-pub fn get_input(year: u32, day: u32) -> Result<String, Box<dyn Error>> {
-    if let Ok(input) = get_cached_input(year, day) {
-        return Ok(input);
+pub fn get_input(year: u32, day: u32) -> Result<String, ClientError> {
+    match get_cached_input(year, day) {
+        Ok(input) => return Ok(input),
+        Err(error @ ClientError::Encryption(_)) => return Err(error),
+        Err(_) => (),
     }
 
+    check_puzzle(year, day)?;
+
     println!("Downloading input for {year}-{day}");
     let client = get_aoc_client()?;
 
-    let res = client
-        .get(format!("https://adventofcode.com/{year}/day/{day}/input"))
-        .send()?;
+    let res = send(
+        "input",
+        client.get(format!("{}/{year}/day/{day}/input", base_url())),
+    )?;
 
+    let status = res.status();
     let result = res.text()?;
 
-    if let Err(error) = cache_input(year, day, &result) {
+    check_input(status, &result)?;
+
+    if let Err(error) = write_input_cache(year, day, &result) {
         println!("Error while caching file {}", error)
     }
 
     Ok(result)
 }
 
+/// download the description articles of a puzzle as html
+///
+/// The page is fetched again as long as the cached one does not contain part 2.
+pub fn get_articles(year: u32, day: u32) -> Result<Vec<String>, ClientError> {
+    let cached = std::fs::read_to_string(get_puzzle_path(year, day)?)
+        .map(|html| extract_articles(&html))
+        .ok();
+
+    if let Some(articles) = &cached {
+        if articles.len() == 2 {
+            return Ok(articles.clone());
+        }
+    }
+
+    check_puzzle(year, day)?;
+
+    println!("Downloading description for {year}-{day}");
+    let articles = extract_articles(&get_puzzle_page(year, day)?);
+    if articles.is_empty() {
+        return cached.ok_or(ClientError::UnexpectedResponse(
+            "puzzle page contains no description",
+        ));
+    }
+
+    let html = articles
+        .iter()
+        .map(|article| format!("<article class=\"day-desc\">{article}</article>\n"))
+        .collect::<String>();
+
+    let description = articles_to_markdown(&articles);
+
+    for (path, content) in [
+        (get_puzzle_path(year, day)?, html),
+        (get_description_path(year, day)?, description),
+    ] {
+        if let Err(error) = write_fetched(&path, content) {
+            println!("Error while caching file {}", error)
+        }
+    }
+
+    Ok(articles)
+}
+
+/// download the page of a puzzle
+fn get_puzzle_page(year: u32, day: u32) -> Result<String, ClientError> {
+    let client = get_aoc_client()?;
+
+    let res = send(
+        "puzzle",
+        client.get(format!("{}/{year}/day/{day}", base_url())),
+    )?;
+
+    match res.status() {
+        StatusCode::NOT_FOUND => Err(ClientError::NotFound),
+        status if !status.is_success() => Err(ClientError::Status(status)),
+        _ => Ok(res.text()?),
+    }
+}
+
+/// download puzzle description as markdown
+pub fn get_description(year: u32, day: u32) -> Result<String, ClientError> {
+    Ok(articles_to_markdown(&get_articles(year, day)?))
+}
+
+fn articles_to_markdown(articles: &[String]) -> String {
+    articles
+        .iter()
+        .map(|article| html_to_markdown(article))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// download input and description of every unlocked day of a year into the cache
+///
+/// Several days are downloaded at the same time, the throttle still spaces out the
+/// requests to each endpoint. The downloads always wait for the throttle, as the threads
+/// would otherwise fail each other with `AOC_THROTTLE=fail`. Entries that are cached and
+/// complete are skipped.
+pub fn prefetch_year(year: u32) -> Result<Vec<DayFetch>, ClientError> {
+    let calendar = get_calendar(year)?;
+
+    let days = (1..)
+        .zip(calendar.stars)
+        .filter_map(|(day, stars)| Some((day, stars?)))
+        .collect::<Vec<_>>();
+
+    let queue = Mutex::new(days.into_iter());
+    let results = Mutex::new(Vec::new());
+
+    thread::scope(|scope| {
+        for _ in 0..PREFETCH_THREADS {
+            scope.spawn(|| {
+                THROTTLE_MODE.set(Some(ThrottleMode::Wait));
+
+                loop {
+                    let Some((day, stars)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+
+                    let fetch = DayFetch {
+                        day,
+                        input: prefetch_input(year, day),
+                        description: prefetch_description(year, day, stars),
+                    };
+
+                    results.lock().unwrap().push(fetch);
+                }
+            });
+        }
+    });
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|fetch| fetch.day);
+
+    Ok(results)
+}
+
+fn prefetch_input(year: u32, day: u32) -> FetchStatus {
+    if get_cached_input(year, day).is_ok() {
+        return FetchStatus::Skipped;
+    }
+
+    match get_input(year, day) {
+        Ok(_) => FetchStatus::Fetched,
+        Err(error) => FetchStatus::Failed(error),
+    }
+}
+
+/// the cached description is complete if it contains part 2 once part 1 is solved
+fn is_description_complete(year: u32, day: u32, stars: u8) -> Result<bool, ClientError> {
+    let expected_articles = if stars > 0 { 2 } else { 1 };
+
+    let cached_articles = std::fs::read_to_string(get_puzzle_path(year, day)?)
+        .map_or(0, |html| extract_articles(&html).len());
+
+    Ok(cached_articles >= expected_articles && get_description_path(year, day)?.exists())
+}
+
+fn prefetch_description(year: u32, day: u32, stars: u8) -> FetchStatus {
+    match is_description_complete(year, day, stars) {
+        Ok(true) => return FetchStatus::Skipped,
+        Ok(false) => {}
+        Err(error) => return FetchStatus::Failed(error),
+    }
+
+    match get_articles(year, day) {
+        Ok(_) => FetchStatus::Fetched,
+        Err(error) => FetchStatus::Failed(error),
+    }
+}
+
+/// download the stars of every day of a year
+///
+/// The result is cached for 15 minutes and invalidated when a correct answer is submitted.
+pub fn get_calendar(year: u32) -> Result<Calendar, ClientError> {
+    let path = get_calendar_path(year)?;
+
+    if let Some(calendar) = read_fresh_cache(&path, CALENDAR_MAX_AGE) {
+        if let Ok(calendar) = calendar.parse() {
+            return Ok(calendar);
+        }
+    }
+
+    let client = get_aoc_client()?;
+
+    let res = send("calendar", client.get(format!("{}/{year}", base_url())))?;
+
+    match res.status() {
+        StatusCode::NOT_FOUND => return Err(ClientError::NotFound),
+        status if !status.is_success() => return Err(ClientError::Status(status)),
+        _ => (),
+    }
+
+    let calendar = Calendar::parse(&res.text()?);
+    if calendar.stars.iter().all(Option::is_none) {
+        return Err(ClientError::UnexpectedResponse("page contains no calendar"));
+    }
+
+    if let Err(error) = write_fetched(&path, calendar.to_string()) {
+        println!("Error while caching file {}", error)
+    }
+
+    Ok(calendar)
+}
+
+/// answers of a year that are known to be accepted, without contacting the server
+pub fn get_answers(year: u32) -> Result<Answers, ClientError> {
+    match std::fs::read_to_string(get_answers_path(year)?) {
+        Ok(answers) => answers
+            .parse()
+            .map_err(|_| ClientError::UnexpectedResponse("answers file is damaged")),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// collect the accepted answers of every solved puzzle of a year
+///
+/// The answers are read from the puzzle pages and stored in `{year}.answers` in the cache
+/// folder of the active profile. Pages are only downloaded for days with stars that have
+/// no known answer yet.
+pub fn harvest_answers(year: u32) -> Result<Answers, ClientError> {
+    let mut answers = get_answers(year)?;
+    let calendar = get_calendar(year)?;
+
+    for (i, stars) in calendar.stars.iter().enumerate() {
+        let day = i as u32 + 1;
+        let stars = stars.unwrap_or(0) as usize;
+
+        if answers.is_complete(day, stars) {
+            continue;
+        }
+
+        println!("Downloading answers for {year}-{day}");
+        for (part, answer) in (1..).zip(find_answers(&get_puzzle_page(year, day)?)) {
+            answers.insert(day, part, answer);
+        }
+    }
+
+    write_fetched(&get_answers_path(year)?, answers.to_string())?;
+
+    Ok(answers)
+}
+
+/// download a private leaderboard
+///
+/// The leaderboard is cached for 15 minutes, as requested by the maintainers of adventofcode.com.
+pub fn get_leaderboard(year: u32, id: u64) -> Result<Leaderboard, ClientError> {
+    let path = get_leaderboard_path(year, id)?;
+
+    if let Some(json) = read_fresh_cache(&path, LEADERBOARD_MAX_AGE) {
+        if let Ok(leaderboard) = serde_json::from_str(&json) {
+            return Ok(leaderboard);
+        }
+    }
+
+    let client = get_aoc_client()?;
+
+    let res = send(
+        "leaderboard",
+        client.get(format!(
+            "{}/{year}/leaderboard/private/view/{id}.json",
+            base_url()
+        )),
+    )?;
+
+    match res.status() {
+        StatusCode::NOT_FOUND => return Err(ClientError::NotFound),
+        status if !status.is_success() => return Err(ClientError::Status(status)),
+        _ => (),
+    }
+
+    let json = res.text()?;
+    let leaderboard = serde_json::from_str(&json).map_err(|_| {
+        ClientError::UnexpectedResponse("leaderboard is not accessible with this session")
+    })?;
+
+    if let Err(error) = write_fetched(&path, json) {
+        println!("Error while caching file {}", error)
+    }
+
+    Ok(leaderboard)
+}
+
+/// check that the session of the active profile is logged in
+pub fn check_auth() -> Result<Auth, ClientError> {
+    let session = get_session()?;
+    let client = get_aoc_client()?;
+
+    let res = send("settings", client.get(format!("{}/settings", base_url())))?;
+
+    let user = parse_user(&res.text()?).ok_or(ClientError::SessionExpired)?;
+    let first_seen = session_first_seen(&session)?;
+
+    Ok(Auth {
+        user,
+        first_seen,
+        expires: first_seen + chrono::Duration::from_std(SESSION_LIFETIME).unwrap(),
+    })
+}
+
+/// name of the logged in user shown in the page header
+fn parse_user(html: &str) -> Option<String> {
+    let user_matcher = Regex::new(r#"<div class="user">([^<]+)"#).unwrap();
+
+    let user = user_matcher.captures(html)?[1].trim().to_string();
+
+    Some(user).filter(|user| !user.is_empty())
+}
+
+/// stable identifier of a session that does not reveal it
+fn session_fingerprint(session: &str) -> String {
+    Blake2s256::digest(session.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// first time a session was seen, sessions are stored as hashes only
+fn session_first_seen(session: &str) -> Result<DateTime<Utc>, ClientError> {
+    let path = cache_root().join(".sessions");
+
+    let fingerprint = session_fingerprint(session);
+
+    let sessions = match std::fs::read_to_string(&path) {
+        Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+        sessions => sessions?,
+    };
+
+    let first_seen = sessions.lines().find_map(|line| {
+        let (hash, time) = line.split_once('\t')?;
+        (hash == fingerprint)
+            .then(|| DateTime::parse_from_rfc3339(time).ok())
+            .flatten()
+    });
+
+    if let Some(first_seen) = first_seen {
+        return Ok(first_seen.into());
+    }
+
+    let now = Utc::now();
+    write_cache(
+        &path,
+        format!("{sessions}{fingerprint}\t{}\n", now.to_rfc3339()),
+    )?;
+
+    Ok(now)
+}
+
+/// load all answers submitted for a puzzle
+pub fn get_history(year: u32, day: u32) -> Result<History, ClientError> {
+    Ok(History::load(&get_history_path(year, day)?)?)
+}
+
+/// submit an answer for one part of a puzzle
+///
+/// Answers that are known to be wrong from earlier submissions are rejected without
+/// submitting them, every submitted answer is recorded in the history.
+pub fn submit_answer(year: u32, day: u32, part: u32, answer: &str) -> Result<Verdict, ClientError> {
+    let answer = answer.trim();
+    check_puzzle(year, day)?;
+
+    get_history(year, day)?
+        .check(part, answer)
+        .map_err(ClientError::Rejected)?;
+
+    let client = get_aoc_client()?;
+
+    let res = send_once(
+        "answer",
+        client
+            .post(format!("{}/{year}/day/{day}/answer", base_url()))
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)]),
+    )?;
+
+    let verdict = parse_verdict(&res.text()?)?;
+
+    History::record(
+        &get_history_path(year, day)?,
+        &Submission {
+            time: Utc::now(),
+            part,
+            answer: answer.to_string(),
+            verdict,
+        },
+    )?;
+
+    if verdict == Verdict::Correct {
+        std::fs::remove_file(get_calendar_path(year)?).ok();
+    }
+
+    Ok(verdict)
+}
+
+/// record an answer as the accepted one in `{year}.answers`
+pub fn record_answer(year: u32, day: u32, part: u32, answer: &str) -> Result<(), ClientError> {
+    let mut answers = get_answers(year)?;
+    answers.insert(day, part, answer.trim().to_string());
+
+    Ok(write_cache(&get_answers_path(year)?, answers.to_string())?)
+}
+
+/// interpret the html page returned after submitting an answer
+fn parse_verdict(html: &str) -> Result<Verdict, ClientError> {
+    if html.contains("That's the right answer") {
+        return Ok(Verdict::Correct);
+    }
+
+    if html.contains("That's not the right answer") {
+        return Ok(if html.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if html.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        });
+    }
+
+    if html.contains("You don't seem to be solving the right level") {
+        return Ok(Verdict::AlreadySolved);
+    }
+
+    if html.contains("You gave an answer too recently") {
+        let wait_matcher = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+
+        let wait = match wait_matcher.captures(html) {
+            Some(captures) => {
+                let minutes = captures
+                    .get(1)
+                    .map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+                let seconds = captures[2].parse::<u64>().unwrap();
+                Duration::from_secs(minutes * 60 + seconds)
+            }
+            None => Duration::ZERO,
+        };
+
+        return Ok(Verdict::RateLimited(wait));
+    }
+
+    if html.contains("please identify yourself") {
+        return Err(ClientError::SessionExpired);
+    }
+
+    Err(ClientError::UnexpectedResponse(
+        "unknown answer submission result",
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_get_input_path() {
-        assert_eq!("./.input/2022_01.txt", get_input_path(2022, 1))
+        assert_eq!(
+            Path::new(env!("CARGO_MANIFEST_DIR")).join(".input/2022_01.txt"),
+            get_input_path(2022, 1).unwrap()
+        )
+    }
+
+    #[test]
+    fn test_session_fingerprint() {
+        // stored in `.sessions`, so it must not change between builds
+        assert_eq!(session_fingerprint("test-session"), "e80323026aa6aafc");
+        assert_ne!(
+            session_fingerprint("other"),
+            session_fingerprint("test-session")
+        );
+    }
+
+    #[test]
+    fn test_user_agent() {
+        assert_eq!(user_agent(None), "aoc/0.1.0");
+        assert_eq!(
+            user_agent(Some("jane@example.com")),
+            "aoc/0.1.0 (jane@example.com)"
+        );
+    }
+
+    #[test]
+    fn test_parse_user() {
+        assert_eq!(
+            parse_user(
+                r#"<header><div class="user">Ada Lovelace <span class="star-count">50*</span></div></header>"#
+            ),
+            Some("Ada Lovelace".into())
+        );
+        assert_eq!(
+            parse_user(
+                r#"<div class="user">(anonymous user #12345) <span class="star-count">3*</span></div>"#
+            ),
+            Some("(anonymous user #12345)".into())
+        );
+        assert_eq!(
+            parse_user(r#"<header><a href="/auth/login">[Log In]</a></header>"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_base_url() {
+        assert!(parse_base_url(DEFAULT_BASE_URL).is_ok());
+        assert!(matches!(
+            parse_base_url("not a url"),
+            Err(ClientError::InvalidBaseUrl(url)) if url == "not a url"
+        ));
+    }
+
+    #[test]
+    fn test_check_input() {
+        assert!(check_input(StatusCode::OK, "1000\n2000\n").is_ok());
+        assert!(matches!(
+            check_input(
+                StatusCode::NOT_FOUND,
+                "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+            ),
+            Err(ClientError::NotUnlocked)
+        ));
+        assert!(matches!(
+            check_input(
+                StatusCode::NOT_FOUND,
+                "<!DOCTYPE html>\n<html>404 Not Found</html>"
+            ),
+            Err(ClientError::NotFound)
+        ));
+        assert!(matches!(
+            check_input(
+                StatusCode::BAD_REQUEST,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+            ),
+            Err(ClientError::SessionExpired)
+        ));
+        assert!(matches!(
+            check_input(StatusCode::FORBIDDEN, "Forbidden"),
+            Err(ClientError::Status(StatusCode::FORBIDDEN))
+        ));
+        assert!(matches!(
+            check_input(StatusCode::OK, "<!DOCTYPE html>\n<html></html>"),
+            Err(ClientError::UnexpectedResponse(_))
+        ));
+        assert!(matches!(
+            check_input(StatusCode::OK, "<html><body>error</body></html>"),
+            Err(ClientError::UnexpectedResponse(_))
+        ));
+        assert!(check_input(StatusCode::OK, "<<><>>><<>>\n").is_ok());
+        assert!(matches!(
+            check_input(StatusCode::OK, ""),
+            Err(ClientError::UnexpectedResponse(_))
+        ));
+    }
+
+    #[test]
+    fn test_is_transient() {
+        assert!(ClientError::Status(StatusCode::BAD_GATEWAY).is_transient());
+        assert!(ClientError::Status(StatusCode::TOO_MANY_REQUESTS).is_transient());
+        assert!(!ClientError::Status(StatusCode::BAD_REQUEST).is_transient());
+        assert!(!ClientError::SessionExpired.is_transient());
+        assert!(!ClientError::NotUnlocked.is_transient());
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict("<article><p>That's the right answer!  You are one gold star closer to saving your vacation.</p></article>").unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict("<article><p>That's not the right answer; your answer is too high.  If you're stuck, ...</p></article>").unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict(
+                "<article><p>That's not the right answer; your answer is too low.</p></article>"
+            )
+            .unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict(
+                "<article><p>That's not the right answer.  If you're stuck, ...</p></article>"
+            )
+            .unwrap(),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_verdict("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>").unwrap(),
+            Verdict::AlreadySolved
+        );
+        assert!(parse_verdict("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_verdict_from_str() {
+        for verdict in [
+            Verdict::Correct,
+            Verdict::Wrong,
+            Verdict::TooHigh,
+            Verdict::TooLow,
+            Verdict::AlreadySolved,
+            Verdict::RateLimited(Duration::from_secs(65)),
+        ] {
+            assert_eq!(verdict.to_string().parse::<Verdict>(), Ok(verdict));
+        }
+        assert!("maybe".parse::<Verdict>().is_err());
+    }
+
+    #[test]
+    fn test_parse_verdict_rate_limited() {
+        assert_eq!(
+            parse_verdict("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.</p></article>").unwrap(),
+            Verdict::RateLimited(Duration::from_secs(34))
+        );
+        assert_eq!(
+            parse_verdict("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.</p></article>").unwrap(),
+            Verdict::RateLimited(Duration::from_secs(242))
+        );
     }
 }
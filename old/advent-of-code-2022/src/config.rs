@@ -0,0 +1,150 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    env, fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+/// user configuration, read from `~/.config/aoc/config`
+///
+/// The file contains `key = value` pairs, settings of named profiles are placed
+/// in a section with the name of the profile:
+///
+/// ```text
+/// session = 53616c74...
+///
+/// [alice]
+/// session = 6b6f6b6f...
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Config {
+    sections: HashMap<Option<String>, HashMap<String, String>>,
+}
+
+impl Config {
+    /// location of the config file, can be changed with the `AOC_CONFIG` environment variable
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = env::var_os("AOC_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+
+        let config_home = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+
+        Some(config_home.join("aoc").join("config"))
+    }
+
+    /// load the config file, a missing file is an empty config
+    pub fn load() -> Result<Config, io::Error> {
+        let Some(path) = Config::path() else {
+            return Ok(Config::default());
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Config::default()),
+            content => content?,
+        };
+
+        warn_if_readable_by_others(&path);
+
+        Ok(Config::parse(&content))
+    }
+
+    pub fn parse(content: &str) -> Config {
+        let mut config = Config::default();
+        let mut section = None;
+
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.trim().to_string());
+            } else if let Some((key, value)) = line.split_once('=') {
+                config
+                    .sections
+                    .entry(section.clone())
+                    .or_default()
+                    .insert(key.trim().to_string(), value.trim().to_string());
+            }
+        }
+
+        config
+    }
+
+    /// value of a setting of a profile, `None` selects the default profile
+    pub fn get(&self, profile: Option<&str>, key: &str) -> Option<&str> {
+        self.sections
+            .get(&profile.map(str::to_string))?
+            .get(key)
+            .map(String::as_str)
+    }
+}
+
+/// whether a warning about the file was not printed yet, the config is loaded by several modules
+#[cfg(unix)]
+fn first_warning(path: &Path) -> bool {
+    static WARNED: Mutex<BTreeSet<PathBuf>> = Mutex::new(BTreeSet::new());
+
+    WARNED
+        .lock()
+        .unwrap_or_else(|error| error.into_inner())
+        .insert(path.to_path_buf())
+}
+
+#[cfg(unix)]
+fn warn_if_readable_by_others(path: &Path) {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Ok(metadata) = fs::metadata(path) {
+        if metadata.permissions().mode() & 0o077 != 0 && first_warning(path) {
+            eprintln!(
+                "warning: {} contains session tokens and can be read by other users, run `chmod 600 {}`",
+                path.display(),
+                path.display()
+            );
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn warn_if_readable_by_others(_path: &Path) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_first_warning() {
+        let path = Path::new("/tmp/aoc-config-warning");
+
+        assert!(first_warning(path));
+        assert!(!first_warning(path));
+        assert!(first_warning(Path::new("/tmp/aoc-other-config")));
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            "# sessions
+session = abc
+
+[alice]
+session= def
+contact =alice@example.com
+",
+        );
+
+        assert_eq!(config.get(None, "session"), Some("abc"));
+        assert_eq!(config.get(Some("alice"), "session"), Some("def"));
+        assert_eq!(
+            config.get(Some("alice"), "contact"),
+            Some("alice@example.com")
+        );
+        assert_eq!(config.get(None, "contact"), None);
+        assert_eq!(config.get(Some("bob"), "session"), None);
+    }
+}
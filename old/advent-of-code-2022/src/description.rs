@@ -0,0 +1,365 @@
+use regex::Regex;
+
+const BOLD: &str = "\x1b[1m";
+const CODE: &str = "\x1b[36m";
+const RESET: &str = "\x1b[0m";
+
+/// extract the `<article class="day-desc">` sections of a puzzle page
+pub fn extract_articles(html: &str) -> Vec<String> {
+    let article_matcher = Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+
+    article_matcher
+        .captures_iter(html)
+        .map(|captures| captures[1].to_string())
+        .collect()
+}
+
+/// extract the accepted answers shown on the page of a (partially) solved puzzle
+pub fn find_answers(html: &str) -> Vec<String> {
+    let answer_matcher = Regex::new(r"Your puzzle answer was <code>(.*?)</code>").unwrap();
+
+    answer_matcher
+        .captures_iter(html)
+        .map(|captures| decode_entities(&captures[1]))
+        .collect()
+}
+
+/// title of the puzzle from the heading of the first article, e.g. `Calorie Counting`
+pub fn find_title(article: &str) -> Option<String> {
+    let title_matcher = Regex::new(r"<h2>--- Day \d+: (.*?) ---</h2>").unwrap();
+
+    title_matcher
+        .captures(article)
+        .map(|captures| decode_entities(&captures[1]))
+}
+
+/// convert the html of a puzzle description to markdown
+pub fn html_to_markdown(html: &str) -> String {
+    let tag_matcher = Regex::new(r#"<(/?)([a-z0-9]+)(?:\s+href="([^"]*)")?[^>]*>"#).unwrap();
+
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<String> = Vec::new();
+    let mut last_end = 0;
+
+    for captures in tag_matcher.captures_iter(html) {
+        let tag = captures.get(0).unwrap();
+        push_text(&mut markdown, &html[last_end..tag.start()], in_pre);
+        last_end = tag.end();
+
+        let closing = &captures[1] == "/";
+
+        // inside of code blocks only the text is kept
+        if in_pre && &captures[2] != "pre" {
+            continue;
+        }
+
+        match (&captures[2], closing) {
+            ("h2", false) => {
+                end_line(&mut markdown, 2);
+                markdown.push_str("## ");
+            }
+            ("h2" | "p" | "ul", _) => end_line(&mut markdown, 2),
+            ("pre", false) => {
+                in_pre = true;
+                end_line(&mut markdown, 2);
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                end_line(&mut markdown, 1);
+                markdown.push_str("```\n\n");
+            }
+            ("li", false) => {
+                end_line(&mut markdown, 1);
+                markdown.push_str("- ");
+            }
+            ("code", _) => markdown.push('`'),
+            ("em", _) => markdown.push_str("**"),
+            ("a", false) => {
+                links.push(captures.get(3).map_or("", |m| m.as_str()).to_string());
+                markdown.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                markdown.push_str(&format!("]({href})"));
+            }
+            _ => (),
+        }
+    }
+    push_text(&mut markdown, &html[last_end..], in_pre);
+
+    markdown.trim().to_string() + "\n"
+}
+
+/// append text between two tags, whitespace is only significant in code blocks
+fn push_text(markdown: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+
+    if in_pre {
+        markdown.push_str(&text);
+    } else if !(text.trim().is_empty() && (markdown.is_empty() || markdown.ends_with('\n'))) {
+        markdown.push_str(&text.replace('\n', " "));
+    }
+}
+
+/// make sure the markdown ends with at least `newlines` line breaks
+fn end_line(markdown: &mut String, newlines: usize) {
+    if markdown.is_empty() {
+        return;
+    }
+
+    let existing = markdown.len() - markdown.trim_end_matches('\n').len();
+    markdown.truncate(markdown.trim_end().len());
+    markdown.push_str(&"\n".repeat(newlines.max(existing)));
+}
+
+/// find the example input of a puzzle part
+///
+/// This is the first code block that is introduced by a paragraph mentioning an example.
+pub fn find_example_input(article: &str) -> Option<String> {
+    let pre_matcher = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+
+    let example = pre_matcher
+        .captures_iter(article)
+        .find(|captures| {
+            let before = &article[..captures.get(0).unwrap().start()];
+
+            before
+                .rfind("<p>")
+                .is_some_and(|start| before[start..].to_lowercase().contains("example"))
+        })
+        .map(|captures| strip_tags(&captures[1]).trim_end_matches('\n').to_string());
+
+    example
+}
+
+/// find the expected answer for the example of a puzzle part
+///
+/// The answer is the last emphasized code in the description. Nothing is returned if
+/// it does not look like a single value.
+pub fn find_expected_answer(article: &str) -> Option<String> {
+    let answer_matcher =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let captures = answer_matcher.captures_iter(article).last()?;
+    let answer = strip_tags(captures.get(1).or(captures.get(2))?.as_str());
+
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return None;
+    }
+
+    Some(answer)
+}
+
+fn strip_tags(html: &str) -> String {
+    let tag_matcher = Regex::new(r"<[^>]*>").unwrap();
+
+    decode_entities(&tag_matcher.replace_all(html, ""))
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// render a markdown puzzle description for the terminal
+///
+/// Paragraphs are wrapped to `width`, emphasis and inline code are highlighted
+/// and code blocks are printed unchanged.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut output = String::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            output.push_str(&format!("    {line}\n"));
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            output.push_str(&format!("{BOLD}{heading}{RESET}\n"));
+        } else if let Some(item) = line.strip_prefix("- ") {
+            output.push_str(&wrap(item, width, "  - ", "    "));
+        } else if line.is_empty() {
+            output.push('\n');
+        } else {
+            output.push_str(&wrap(line, width, "", ""));
+        }
+    }
+
+    output.trim_end().to_string() + "\n"
+}
+
+/// wrap a line of markdown text, replacing markup by terminal escape codes
+fn wrap(text: &str, width: usize, first_indent: &str, indent: &str) -> String {
+    let mut output = String::from(first_indent);
+    let mut line_width = first_indent.len();
+    let mut line_empty = true;
+    let mut bold = false;
+    let mut code = false;
+
+    for word in text.split_whitespace() {
+        let visible = word.replace("**", "").replace('`', "").chars().count();
+
+        if !line_empty && line_width + 1 + visible > width {
+            output.push_str(RESET);
+            output.push('\n');
+            output.push_str(indent);
+            if bold {
+                output.push_str(BOLD);
+            }
+            if code {
+                output.push_str(CODE);
+            }
+            line_width = indent.len();
+            line_empty = true;
+        }
+
+        if !line_empty {
+            output.push(' ');
+            line_width += 1;
+        }
+
+        let mut chars = word.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    bold = !bold;
+                    output.push_str(if bold { BOLD } else { RESET });
+                    if !bold && code {
+                        output.push_str(CODE);
+                    }
+                }
+                '`' => {
+                    code = !code;
+                    output.push_str(if code { CODE } else { RESET });
+                    if !code && bold {
+                        output.push_str(BOLD);
+                    }
+                }
+                _ => output.push(c),
+            }
+        }
+        line_width += visible;
+        line_empty = false;
+    }
+
+    output.push_str(RESET);
+    output.push('\n');
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_HTML: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Calorie Counting ---</h2><p>For example, the Elves take turns writing down the number of <a href="/2022/about">Calories</a> contained:</p>
+<pre><code>1000
+2000
+</code></pre>
+<ul>
+<li>The first Elf is carrying <code>1000</code>.</li>
+</ul>
+<p>This Elf is carrying <code><em>24000</em></code> Calories &amp; more.</p>
+</article>
+<p>Your puzzle answer was <code>69289</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Find the top <em>three</em> Elves.</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract_articles() {
+        let articles = extract_articles(TEST_HTML);
+
+        assert_eq!(articles.len(), 2);
+        assert!(articles[1].starts_with("<h2 id=\"part2\">"));
+    }
+
+    #[test]
+    fn test_find_answers() {
+        assert_eq!(find_answers(TEST_HTML), vec!["69289"]);
+        assert!(find_answers("<main></main>").is_empty());
+    }
+
+    #[test]
+    fn test_find_title() {
+        let articles = extract_articles(TEST_HTML);
+
+        assert_eq!(
+            find_title(&articles[0]),
+            Some(String::from("Calorie Counting"))
+        );
+        assert_eq!(find_title(&articles[1]), None);
+    }
+
+    #[test]
+    fn test_html_to_markdown() {
+        let articles = extract_articles(TEST_HTML);
+
+        assert_eq!(
+            html_to_markdown(&articles[0]),
+            "## --- Day 1: Calorie Counting ---
+
+For example, the Elves take turns writing down the number of [Calories](/2022/about) contained:
+
+```
+1000
+2000
+```
+
+- The first Elf is carrying `1000`.
+
+This Elf is carrying `**24000**` Calories & more.
+"
+        );
+        assert_eq!(
+            html_to_markdown(&articles[1]),
+            "## --- Part Two ---\n\nFind the top **three** Elves.\n"
+        );
+    }
+
+    #[test]
+    fn test_find_example_input() {
+        let articles = extract_articles(TEST_HTML);
+
+        assert_eq!(find_example_input(&articles[0]), Some("1000\n2000".into()));
+        assert_eq!(find_example_input(&articles[1]), None);
+        assert_eq!(
+            find_example_input("<p>Consider the map:</p>\n<pre><code>#.#</code></pre>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_expected_answer() {
+        let articles = extract_articles(TEST_HTML);
+
+        assert_eq!(find_expected_answer(&articles[0]), Some("24000".into()));
+        assert_eq!(find_expected_answer(&articles[1]), None);
+        assert_eq!(
+            find_expected_answer("<p>The message is <em><code>CMZ</code></em>.</p>"),
+            Some("CMZ".into())
+        );
+        assert_eq!(
+            find_expected_answer("<p>Draw <code><em>#.#\n.#.</em></code>.</p>"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render("Find the top **three** Elves.\n\n```\n1 2\n```\n", 16);
+
+        assert_eq!(
+            rendered,
+            format!("Find the top{RESET}\n{BOLD}three{RESET} Elves.{RESET}\n\n    1 2\n")
+        );
+    }
+}
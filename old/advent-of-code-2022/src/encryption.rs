@@ -0,0 +1,241 @@
+use crate::config::Config;
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use std::{
+    env,
+    error::Error,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// start of every encrypted file
+const MAGIC: &[u8] = b"aoc-encrypted-1\n";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum EncryptionError {
+    /// a file is encrypted, but no key is configured
+    MissingKey,
+    /// the file could not be decrypted, either the key is wrong or the file is damaged
+    WrongKey,
+    /// the file is too short to be encrypted data
+    Malformed,
+    Io(io::Error),
+}
+
+impl fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionError::MissingKey => write!(f, "file is encrypted, but no key is set"),
+            EncryptionError::WrongKey => write!(f, "file could not be decrypted with the key"),
+            EncryptionError::Malformed => write!(f, "encrypted file is damaged"),
+            EncryptionError::Io(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for EncryptionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EncryptionError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EncryptionError {
+    fn from(error: io::Error) -> Self {
+        EncryptionError::Io(error)
+    }
+}
+
+/// secret used to encrypt cached inputs
+///
+/// Every file gets its own salt, the actual key is derived from the secret and the
+/// salt with argon2.
+#[derive(Clone)]
+pub struct CacheKey {
+    secret: Vec<u8>,
+}
+
+impl fmt::Debug for CacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CacheKey(..)")
+    }
+}
+
+impl CacheKey {
+    pub fn from_passphrase(passphrase: &str) -> CacheKey {
+        CacheKey {
+            secret: passphrase.as_bytes().to_vec(),
+        }
+    }
+
+    /// use the content of a file as secret
+    pub fn from_key_file(path: &Path) -> Result<CacheKey, io::Error> {
+        let secret = fs::read(path)?;
+
+        if secret.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("key file {} is empty", path.display()),
+            ));
+        }
+
+        Ok(CacheKey { secret })
+    }
+
+    /// key configured with `AOC_CACHE_KEY_FILE` or `AOC_CACHE_PASSPHRASE`
+    ///
+    /// The config file settings `cache_key_file` and `cache_passphrase` are used when the
+    /// environment variables are not set. Without a key the cache is not encrypted.
+    pub fn from_env() -> Result<Option<CacheKey>, io::Error> {
+        let config = Config::load().unwrap_or_default();
+
+        let key_file = env::var_os("AOC_CACHE_KEY_FILE")
+            .map(PathBuf::from)
+            .or_else(|| config.get(None, "cache_key_file").map(PathBuf::from));
+        if let Some(path) = key_file {
+            return CacheKey::from_key_file(&path).map(Some);
+        }
+
+        let passphrase = env::var("AOC_CACHE_PASSPHRASE")
+            .ok()
+            .or_else(|| config.get(None, "cache_passphrase").map(str::to_string));
+
+        Ok(passphrase
+            .filter(|passphrase| !passphrase.is_empty())
+            .map(|passphrase| CacheKey::from_passphrase(&passphrase)))
+    }
+
+    fn cipher(&self, salt: &[u8]) -> ChaCha20Poly1305 {
+        let mut key = Key::default();
+        Argon2::default()
+            .hash_password_into(&self.secret, salt, &mut key)
+            .expect("invalid argon2 parameters");
+
+        ChaCha20Poly1305::new(&key)
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let salt: [u8; SALT_LEN] = rand_bytes();
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+
+        let ciphertext = self
+            .cipher(&salt)
+            .encrypt(&nonce, plaintext)
+            .expect("encryption failed");
+
+        [MAGIC, &salt, &nonce, &ciphertext].concat()
+    }
+
+    pub fn decrypt(&self, data: &[u8]) -> Result<Vec<u8>, EncryptionError> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .filter(|data| data.len() >= SALT_LEN + NONCE_LEN)
+            .ok_or(EncryptionError::Malformed)?;
+
+        let (salt, data) = data.split_at(SALT_LEN);
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+
+        self.cipher(salt)
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| EncryptionError::WrongKey)
+    }
+}
+
+fn rand_bytes<const N: usize>() -> [u8; N] {
+    use chacha20poly1305::aead::rand_core::RngCore;
+
+    let mut bytes = [0; N];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(MAGIC)
+}
+
+/// encrypt every plaintext `.txt` file in a folder, returns the files that were encrypted
+pub fn encrypt_folder(folder: &Path, key: &CacheKey) -> Result<Vec<PathBuf>, EncryptionError> {
+    let mut encrypted = Vec::new();
+
+    let mut paths = fs::read_dir(folder)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    paths.sort();
+
+    for path in paths {
+        if !path.is_file() || path.extension().is_none_or(|extension| extension != "txt") {
+            continue;
+        }
+
+        let data = fs::read(&path)?;
+        if is_encrypted(&data) {
+            continue;
+        }
+
+        // write next to the original first, so an interrupted migration loses nothing
+        let temporary = path.with_extension("txt.tmp");
+        fs::write(&temporary, key.encrypt(&data))?;
+        fs::rename(&temporary, &path)?;
+
+        encrypted.push(path);
+    }
+
+    Ok(encrypted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let key = CacheKey::from_passphrase("correct horse");
+        let encrypted = key.encrypt(b"1000\n2000\n");
+
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(b"1000\n2000\n"));
+        assert_ne!(encrypted, key.encrypt(b"1000\n2000\n"));
+        assert_eq!(key.decrypt(&encrypted).unwrap(), b"1000\n2000\n");
+
+        assert!(matches!(
+            CacheKey::from_passphrase("battery staple").decrypt(&encrypted),
+            Err(EncryptionError::WrongKey)
+        ));
+        assert!(matches!(
+            key.decrypt(&encrypted[..MAGIC.len() + 4]),
+            Err(EncryptionError::Malformed)
+        ));
+    }
+
+    #[test]
+    fn test_encrypt_folder() {
+        let folder = env::temp_dir().join(format!("aoc-encrypt-{}", std::process::id()));
+        fs::create_dir_all(&folder).unwrap();
+        fs::write(folder.join("2022_01.txt"), "1000\n").unwrap();
+        fs::write(folder.join("2022_01.md"), "# Day 1\n").unwrap();
+
+        let key = CacheKey::from_passphrase("correct horse");
+
+        assert_eq!(
+            encrypt_folder(&folder, &key).unwrap(),
+            vec![folder.join("2022_01.txt")]
+        );
+        assert!(encrypt_folder(&folder, &key).unwrap().is_empty());
+
+        let data = fs::read(folder.join("2022_01.txt")).unwrap();
+        assert_eq!(key.decrypt(&data).unwrap(), b"1000\n");
+        assert_eq!(
+            fs::read_to_string(folder.join("2022_01.md")).unwrap(),
+            "# Day 1\n"
+        );
+
+        fs::remove_dir_all(&folder).unwrap();
+    }
+}
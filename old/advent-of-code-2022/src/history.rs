@@ -0,0 +1,187 @@
+use crate::client::Verdict;
+use chrono::{DateTime, Utc};
+use std::{
+    error::Error,
+    fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+/// an answer that was submitted for a puzzle part
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Submission {
+    pub time: DateTime<Utc>,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// reasons to not submit an answer, based on earlier submissions
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Rejection {
+    /// the part was already solved with this answer
+    AlreadySolved(String),
+    /// the answer was already submitted and was wrong
+    AlreadyWrong,
+    /// the answer is at least as high as an answer that was too high
+    TooHigh(i64),
+    /// the answer is at most as low as an answer that was too low
+    TooLow(i64),
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) => write!(f, "already solved with {answer}"),
+            Rejection::AlreadyWrong => write!(f, "answer was already submitted and is wrong"),
+            Rejection::TooHigh(bound) => write!(f, "answer must be lower than {bound}"),
+            Rejection::TooLow(bound) => write!(f, "answer must be higher than {bound}"),
+        }
+    }
+}
+
+impl Error for Rejection {}
+
+/// log of all answers submitted for a puzzle
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// read the history from a log file, a missing file is an empty history
+    pub fn load(path: &Path) -> Result<History, io::Error> {
+        let log = match fs::read_to_string(path) {
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            log => log?,
+        };
+
+        let submissions = log
+            .lines()
+            .map(|line| {
+                parse_submission(line)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, line.to_string()))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(History { submissions })
+    }
+
+    /// append a submission to the log file
+    pub fn record(path: &Path, submission: &Submission) -> Result<(), io::Error> {
+        if let Some(folder) = path.parent() {
+            fs::create_dir_all(folder)?;
+        }
+
+        let mut log = OpenOptions::new().create(true).append(true).open(path)?;
+
+        writeln!(
+            log,
+            "{}\t{}\t{}\t{}",
+            submission.time.to_rfc3339(),
+            submission.part,
+            submission.verdict,
+            submission.answer
+        )
+    }
+
+    /// check whether an answer can be right, given the earlier submissions
+    pub fn check(&self, part: u32, answer: &str) -> Result<(), Rejection> {
+        let submissions = self.submissions.iter().filter(|s| s.part == part);
+        let value = answer.parse::<i64>().ok();
+
+        for submission in submissions {
+            let earlier = submission.answer.parse::<i64>().ok();
+
+            match (submission.verdict, value, earlier) {
+                (Verdict::Correct, _, _) => {
+                    return Err(Rejection::AlreadySolved(submission.answer.clone()))
+                }
+                (Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow, _, _)
+                    if submission.answer == answer =>
+                {
+                    return Err(Rejection::AlreadyWrong)
+                }
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Err(Rejection::TooHigh(bound))
+                }
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Err(Rejection::TooLow(bound))
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_submission(line: &str) -> Option<Submission> {
+    let mut fields = line.splitn(4, '\t');
+
+    Some(Submission {
+        time: DateTime::parse_from_rfc3339(fields.next()?).ok()?.into(),
+        part: fields.next()?.parse().ok()?,
+        verdict: fields.next()?.parse().ok()?,
+        answer: fields.next()?.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn submission(part: u32, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            time: DateTime::parse_from_rfc3339("2022-12-01T05:00:00+00:00")
+                .unwrap()
+                .into(),
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn test_parse_submission() {
+        assert_eq!(
+            parse_submission("2022-12-01T05:00:00+00:00\t1\ttoo-high\t1000"),
+            Some(submission(1, "1000", Verdict::TooHigh))
+        );
+        assert_eq!(
+            parse_submission("2022-12-01T05:00:00+00:00\t2\trate-limited 30\tabc def"),
+            Some(submission(
+                2,
+                "abc def",
+                Verdict::RateLimited(Duration::from_secs(30))
+            ))
+        );
+        assert_eq!(parse_submission("2022-12-01T05:00:00+00:00\t1"), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let history = History {
+            submissions: vec![
+                submission(1, "1000", Verdict::TooHigh),
+                submission(1, "10", Verdict::TooLow),
+                submission(1, "500", Verdict::Wrong),
+                submission(1, "600", Verdict::RateLimited(Duration::from_secs(30))),
+                submission(2, "ABC", Verdict::Correct),
+            ],
+        };
+
+        assert_eq!(history.check(1, "1000"), Err(Rejection::AlreadyWrong));
+        assert_eq!(history.check(1, "1500"), Err(Rejection::TooHigh(1000)));
+        assert_eq!(history.check(1, "5"), Err(Rejection::TooLow(10)));
+        assert_eq!(history.check(1, "500"), Err(Rejection::AlreadyWrong));
+        assert_eq!(history.check(1, "600"), Ok(()));
+        assert_eq!(history.check(1, "ABC"), Ok(()));
+        assert_eq!(
+            history.check(2, "XYZ"),
+            Err(Rejection::AlreadySolved("ABC".into()))
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{puzzle::PuzzleId, unlock::unlock_time};
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// private leaderboard as returned by `/{year}/leaderboard/private/view/{id}.json`
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub global_score: u32,
+    pub last_star_ts: i64,
+    /// stars by day and part
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    pub star_index: u64,
+}
+
+impl Leaderboard {
+    /// members ordered by local score, highest first
+    pub fn ranking(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+
+        members
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    /// number of stars (0, 1 or 2) collected for a day
+    pub fn stars_on(&self, day: u32) -> usize {
+        self.completion_day_level
+            .get(&day.to_string())
+            .map_or(0, |parts| parts.len())
+    }
+
+    /// time between the puzzle unlock and getting the star for a part
+    pub fn time_to_star(&self, puzzle: PuzzleId, part: u32) -> Option<Duration> {
+        let star = self
+            .completion_day_level
+            .get(&puzzle.day.to_string())?
+            .get(&part.to_string())?;
+
+        Some(Duration::seconds(
+            star.get_star_ts - unlock_time(puzzle).timestamp(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_JSON: &str = r#"{"event":"2022","owner_id":1,"members":{
+        "1":{"id":1,"name":"Ada","stars":3,"local_score":5,"global_score":0,"last_star_ts":1669960800,
+            "completion_day_level":{"1":{"1":{"get_star_ts":1669871100,"star_index":1},"2":{"get_star_ts":1669874700,"star_index":2}},
+                "2":{"1":{"get_star_ts":1669960800,"star_index":3}}}},
+        "2":{"id":2,"name":null,"stars":1,"local_score":6,"global_score":0,"last_star_ts":1669870860,
+            "completion_day_level":{"1":{"1":{"get_star_ts":1669870860,"star_index":0}}}}}}"#;
+
+    #[test]
+    fn test_ranking() {
+        let leaderboard: Leaderboard = serde_json::from_str(TEST_JSON).unwrap();
+
+        let ranking = leaderboard.ranking();
+        assert_eq!(ranking[0].display_name(), "(anonymous user #2)");
+        assert_eq!(ranking[1].display_name(), "Ada");
+    }
+
+    #[test]
+    fn test_time_to_star() {
+        let leaderboard: Leaderboard = serde_json::from_str(TEST_JSON).unwrap();
+        let member = &leaderboard.members["1"];
+
+        assert_eq!(member.stars_on(1), 2);
+        assert_eq!(member.stars_on(2), 1);
+        assert_eq!(member.stars_on(3), 0);
+        let day = |day| PuzzleId { year: 2022, day };
+        assert_eq!(member.time_to_star(day(1), 1), Some(Duration::minutes(5)));
+        assert_eq!(member.time_to_star(day(1), 2), Some(Duration::minutes(65)));
+        assert_eq!(member.time_to_star(day(2), 1), Some(Duration::hours(1)));
+        assert_eq!(member.time_to_star(day(2), 2), None);
+    }
+}
@@ -1,2 +1,15 @@
+pub mod answers;
+pub mod bench;
+pub mod cache;
+pub mod calendar;
 pub mod client;
+pub mod config;
+pub mod description;
+pub mod encryption;
+pub mod history;
+pub mod leaderboard;
 pub mod point;
+pub mod profile;
+pub mod puzzle;
+pub mod throttle;
+pub mod unlock;
@@ -0,0 +1,107 @@
+use crate::{client::ClientError, config::Config};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+/// account used to talk to adventofcode.com
+///
+/// Every named profile has its own session and its own subfolder in the input cache,
+/// since inputs differ per account. The default profile uses `AOC_SESSION` and the
+/// cache folder itself.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Profile {
+    name: Option<String>,
+}
+
+impl Profile {
+    /// create a named profile, names may only contain letters, digits, `-` and `_`
+    pub fn new(name: &str) -> Result<Profile, String> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "invalid profile name '{name}', use letters, digits, '-' and '_'"
+            ));
+        }
+
+        Ok(Profile {
+            name: Some(name.to_string()),
+        })
+    }
+
+    /// profile selected by the `AOC_PROFILE` environment variable
+    pub fn active() -> Result<Profile, ClientError> {
+        match env::var("AOC_PROFILE") {
+            Ok(name) if !name.is_empty() => {
+                Profile::new(&name).map_err(ClientError::InvalidProfile)
+            }
+            _ => Ok(Profile::default()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or("default")
+    }
+
+    /// environment variable containing the session of the profile, e.g. `AOC_SESSION_ALICE`
+    pub fn session_variable(&self) -> String {
+        match &self.name {
+            Some(name) => format!("AOC_SESSION_{}", name.to_uppercase().replace('-', "_")),
+            None => String::from("AOC_SESSION"),
+        }
+    }
+
+    /// session from the environment, or from the config file as a fallback
+    pub fn session(&self) -> Option<String> {
+        env::var(self.session_variable()).ok().or_else(|| {
+            Config::load()
+                .ok()?
+                .get(self.name.as_deref(), "session")
+                .map(str::to_string)
+        })
+    }
+
+    /// folder of the profile inside of the input cache
+    pub fn cache_folder(&self, root: &Path) -> PathBuf {
+        match &self.name {
+            Some(name) => root.join(name),
+            None => root.to_path_buf(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(Profile::new("alice").is_ok());
+        assert!(Profile::new("team-b_2").is_ok());
+        assert!(Profile::new("").is_err());
+        assert!(Profile::new("../alice").is_err());
+    }
+
+    #[test]
+    fn test_session_variable() {
+        assert_eq!(Profile::default().session_variable(), "AOC_SESSION");
+        assert_eq!(
+            Profile::new("team-b").unwrap().session_variable(),
+            "AOC_SESSION_TEAM_B"
+        );
+    }
+
+    #[test]
+    fn test_cache_folder() {
+        let root = Path::new("./.input/");
+
+        assert_eq!(Profile::default().cache_folder(root), root);
+        assert_eq!(
+            Profile::new("alice").unwrap().cache_folder(root),
+            Path::new("./.input/alice")
+        );
+    }
+}
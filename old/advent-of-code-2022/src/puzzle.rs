@@ -0,0 +1,332 @@
+use crate::unlock::unlock_time;
+use chrono::{DateTime, Datelike, Duration, Utc};
+use std::{error::Error, fmt, str::FromStr};
+
+/// the first advent of code
+pub const FIRST_YEAR: u32 = 2015;
+
+/// the last puzzle day of a year, since 2025 there are 12 puzzles instead of 25
+pub fn last_day(year: u32) -> u32 {
+    if year < 2025 {
+        25
+    } else {
+        12
+    }
+}
+
+/// a puzzle, identified by year and day
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct PuzzleId {
+    pub year: u32,
+    pub day: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PuzzleIdError {
+    /// the text is not a day, `year/day` or `year-day`
+    Malformed(String),
+    /// there was no advent of code in this year
+    InvalidYear(u32),
+    /// the year has no puzzle on this day, see [`last_day`]
+    InvalidDay(u32, u32),
+    /// the puzzle unlocks at the given time
+    NotUnlocked(PuzzleId, DateTime<Utc>),
+}
+
+impl fmt::Display for PuzzleIdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PuzzleIdError::Malformed(text) => write!(
+                f,
+                "invalid puzzle '{text}', use a day, year/day or year-day"
+            ),
+            PuzzleIdError::InvalidYear(year) => {
+                write!(f, "no puzzles in {year}, the first year is {FIRST_YEAR}")
+            }
+            PuzzleIdError::InvalidDay(year, day) => write!(
+                f,
+                "{day} is not a puzzle day of {year}, use 1 to {}",
+                last_day(*year)
+            ),
+            PuzzleIdError::NotUnlocked(puzzle, unlock) => {
+                write!(f, "puzzle {puzzle} unlocks at {unlock}")
+            }
+        }
+    }
+}
+
+impl Error for PuzzleIdError {}
+
+impl PuzzleId {
+    /// check that the puzzle exists, it is not checked that it is unlocked yet
+    pub fn new(year: u32, day: u32) -> Result<PuzzleId, PuzzleIdError> {
+        if year < FIRST_YEAR {
+            return Err(PuzzleIdError::InvalidYear(year));
+        }
+
+        if !(1..=last_day(year)).contains(&day) {
+            return Err(PuzzleIdError::InvalidDay(year, day));
+        }
+
+        Ok(PuzzleId { year, day })
+    }
+
+    /// parse `2022/17` or `2022-17`, a single day like `17` belongs to the latest event at `now`
+    pub fn parse_at(text: &str, now: DateTime<Utc>) -> Result<PuzzleId, PuzzleIdError> {
+        let malformed = || PuzzleIdError::Malformed(text.to_string());
+        let number = |text: &str| text.trim().parse::<u32>().map_err(|_| malformed());
+
+        match text.split_once(['/', '-']) {
+            Some((year, day)) => PuzzleId::new(number(year)?, number(day)?),
+            None => PuzzleId::new(latest_event(now), number(text)?),
+        }
+    }
+
+    /// the newest unlocked puzzle: today's puzzle in December, otherwise the last one of the latest event
+    pub fn latest(now: DateTime<Utc>) -> PuzzleId {
+        let year = latest_event(now);
+        let today = eastern(now);
+
+        let day = if today.year() as u32 == year && today.month() == 12 {
+            today.day().min(last_day(year))
+        } else {
+            last_day(year)
+        };
+
+        PuzzleId { year, day }
+    }
+
+    /// the next puzzle to unlock after `now`
+    pub fn next(now: DateTime<Utc>) -> PuzzleId {
+        let latest = PuzzleId::latest(now);
+
+        if latest.day < last_day(latest.year) {
+            PuzzleId {
+                day: latest.day + 1,
+                ..latest
+            }
+        } else {
+            PuzzleId {
+                year: latest.year + 1,
+                day: 1,
+            }
+        }
+    }
+
+    pub fn unlock_time(&self) -> DateTime<Utc> {
+        unlock_time(*self)
+    }
+
+    pub fn is_unlocked(&self, now: DateTime<Utc>) -> bool {
+        now >= self.unlock_time()
+    }
+
+    /// return the puzzle if it is unlocked at `now`
+    pub fn unlocked(self, now: DateTime<Utc>) -> Result<PuzzleId, PuzzleIdError> {
+        if !self.is_unlocked(now) {
+            return Err(PuzzleIdError::NotUnlocked(self, self.unlock_time()));
+        }
+
+        Ok(self)
+    }
+}
+
+/// `year-day`, padded to the width of the format, e.g. `{puzzle:<8}`
+impl fmt::Display for PuzzleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(&format!("{}-{}", self.year, self.day))
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PuzzleId::parse_at(s, Utc::now())
+    }
+}
+
+/// time in US Eastern time, which is the time zone of the puzzle unlocks
+///
+/// Only the date is used, and the relevant date changes (end of november and december)
+/// are all in standard time, UTC-5.
+fn eastern(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::hours(5)
+}
+
+/// year of the latest event that has started at `now`
+fn latest_event(now: DateTime<Utc>) -> u32 {
+    let today = eastern(now);
+
+    if today.month() == 12 {
+        today.year() as u32
+    } else {
+        today.year() as u32 - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(year: i32, month: u32, day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(year, month, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let now = utc(2022, 12, 17, 12);
+
+        assert_eq!(
+            PuzzleId::parse_at("2021/3", now),
+            Ok(PuzzleId { year: 2021, day: 3 })
+        );
+        assert_eq!(
+            PuzzleId::parse_at("2021-03", now),
+            Ok(PuzzleId { year: 2021, day: 3 })
+        );
+        assert_eq!(
+            PuzzleId::parse_at("17", now),
+            Ok(PuzzleId {
+                year: 2022,
+                day: 17
+            })
+        );
+        assert_eq!(
+            PuzzleId::parse_at("17", utc(2023, 6, 1, 0)),
+            Ok(PuzzleId {
+                year: 2022,
+                day: 17
+            })
+        );
+        assert_eq!(
+            PuzzleId::parse_at("2014/1", now),
+            Err(PuzzleIdError::InvalidYear(2014))
+        );
+        assert_eq!(
+            PuzzleId::parse_at("2022/26", now),
+            Err(PuzzleIdError::InvalidDay(2022, 26))
+        );
+        assert_eq!(
+            PuzzleId::parse_at("0", now),
+            Err(PuzzleIdError::InvalidDay(2022, 0))
+        );
+        assert_eq!(
+            PuzzleId::parse_at("2025/13", now),
+            Err(PuzzleIdError::InvalidDay(2025, 13))
+        );
+        assert_eq!(
+            PuzzleId::parse_at("2025/12", now),
+            Ok(PuzzleId {
+                year: 2025,
+                day: 12
+            })
+        );
+        assert!(matches!(
+            PuzzleId::parse_at("2022/x", now),
+            Err(PuzzleIdError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn test_display() {
+        let puzzle = PuzzleId { year: 2022, day: 7 };
+
+        assert_eq!(puzzle.to_string(), "2022-7");
+        assert_eq!(format!("[{puzzle:<8}]"), "[2022-7  ]");
+        assert_eq!(format!("[{puzzle:>8}]"), "[  2022-7]");
+    }
+
+    #[test]
+    fn test_latest() {
+        // the puzzle of the 17th unlocks at 05:00 UTC
+        assert_eq!(
+            PuzzleId::latest(utc(2022, 12, 17, 4)),
+            PuzzleId {
+                year: 2022,
+                day: 16
+            }
+        );
+        assert_eq!(
+            PuzzleId::latest(utc(2022, 12, 17, 5)),
+            PuzzleId {
+                year: 2022,
+                day: 17
+            }
+        );
+        assert_eq!(
+            PuzzleId::latest(utc(2022, 12, 30, 0)),
+            PuzzleId {
+                year: 2022,
+                day: 25
+            }
+        );
+        assert_eq!(
+            PuzzleId::latest(utc(2022, 12, 1, 4)),
+            PuzzleId {
+                year: 2021,
+                day: 25
+            }
+        );
+        assert_eq!(
+            PuzzleId::latest(utc(2023, 1, 1, 4)),
+            PuzzleId {
+                year: 2022,
+                day: 25
+            }
+        );
+        // since 2025 there are only 12 puzzles
+        assert_eq!(
+            PuzzleId::latest(utc(2025, 12, 20, 0)),
+            PuzzleId {
+                year: 2025,
+                day: 12
+            }
+        );
+        assert_eq!(
+            PuzzleId::latest(utc(2026, 10, 18, 0)),
+            PuzzleId {
+                year: 2025,
+                day: 12
+            }
+        );
+    }
+
+    #[test]
+    fn test_next() {
+        assert_eq!(
+            PuzzleId::next(utc(2022, 12, 17, 4)),
+            PuzzleId {
+                year: 2022,
+                day: 17
+            }
+        );
+        assert_eq!(
+            PuzzleId::next(utc(2022, 12, 17, 5)),
+            PuzzleId {
+                year: 2022,
+                day: 18
+            }
+        );
+        assert_eq!(
+            PuzzleId::next(utc(2022, 11, 20, 0)),
+            PuzzleId { year: 2022, day: 1 }
+        );
+        assert_eq!(
+            PuzzleId::next(utc(2025, 12, 12, 5)),
+            PuzzleId { year: 2026, day: 1 }
+        );
+    }
+
+    #[test]
+    fn test_unlocked() {
+        let puzzle = PuzzleId::new(2022, 17).unwrap();
+
+        assert_eq!(puzzle.unlocked(utc(2022, 12, 17, 5)), Ok(puzzle));
+        assert_eq!(
+            puzzle.unlocked(utc(2022, 12, 17, 4)),
+            Err(PuzzleIdError::NotUnlocked(puzzle, utc(2022, 12, 17, 5)))
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::client::ClientError;
+use std::{
+    collections::HashMap,
+    env, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// minimum time between two requests to the same endpoint
+pub const THROTTLE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// what to do with a request that would violate the throttle
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ThrottleMode {
+    #[default]
+    Wait,
+    Fail,
+}
+
+impl ThrottleMode {
+    /// read the mode from the `AOC_THROTTLE` environment variable (`wait` or `fail`)
+    pub fn from_env() -> ThrottleMode {
+        match env::var("AOC_THROTTLE").as_deref() {
+            Ok("fail") => ThrottleMode::Fail,
+            _ => ThrottleMode::Wait,
+        }
+    }
+}
+
+/// lock per endpoint, serializes the throttled requests of the threads of this process
+fn endpoint_lock(endpoint: &str) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+    let mut locks = LOCKS
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(|error| error.into_inner());
+
+    locks.entry(endpoint.to_string()).or_default().clone()
+}
+
+/// request throttle that persists the time of the last request per endpoint
+///
+/// The timestamps are stored as files in `folder`, so separate processes share the throttle.
+/// Threads of one process reserve their slots one after the other, so concurrent requests are
+/// spaced out as well.
+#[derive(Debug, Clone)]
+pub struct Throttle {
+    folder: PathBuf,
+    interval: Duration,
+    mode: ThrottleMode,
+}
+
+impl Throttle {
+    pub fn new(folder: impl AsRef<Path>, interval: Duration, mode: ThrottleMode) -> Throttle {
+        Self {
+            folder: folder.as_ref().to_path_buf(),
+            interval,
+            mode,
+        }
+    }
+
+    /// wait until a request to `endpoint` is allowed and record it
+    ///
+    /// In `ThrottleMode::Fail` a `ClientError::Throttled` is returned instead of waiting.
+    pub fn acquire(&self, endpoint: &str) -> Result<(), ClientError> {
+        self.acquire_with_mode(endpoint, self.mode)
+    }
+
+    pub fn acquire_with_mode(&self, endpoint: &str, mode: ThrottleMode) -> Result<(), ClientError> {
+        let wait = self.reserve(endpoint, mode)?;
+
+        // sleep without the lock, so other threads can reserve the following slots meanwhile
+        if let Some(wait) = wait {
+            thread::sleep(wait);
+        }
+
+        Ok(())
+    }
+
+    /// record the time the next request to `endpoint` is allowed, returns the wait until then
+    fn reserve(&self, endpoint: &str, mode: ThrottleMode) -> Result<Option<Duration>, ClientError> {
+        let path = self.folder.join(endpoint);
+        let lock = endpoint_lock(endpoint);
+        let _guard = lock.lock().unwrap_or_else(|error| error.into_inner());
+
+        let last_request = fs::read_to_string(&path)
+            .ok()
+            .and_then(|millis| millis.trim().parse::<u64>().ok())
+            .map(|millis| UNIX_EPOCH + Duration::from_millis(millis));
+
+        let now = SystemTime::now();
+        let wait = remaining_wait(last_request, now, self.interval);
+
+        if let (ThrottleMode::Fail, Some(wait)) = (mode, wait) {
+            return Err(ClientError::Throttled(wait));
+        }
+
+        let request = (now + wait.unwrap_or_default())
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        fs::create_dir_all(&self.folder)?;
+        fs::write(path, request.as_millis().to_string())?;
+
+        Ok(wait)
+    }
+}
+
+/// time until the next request is allowed, if any
+///
+/// The last request can be in the future, if it was reserved by a thread that is still waiting.
+fn remaining_wait(
+    last_request: Option<SystemTime>,
+    now: SystemTime,
+    interval: Duration,
+) -> Option<Duration> {
+    match now.duration_since(last_request?) {
+        Ok(elapsed) => interval.checked_sub(elapsed).filter(|wait| !wait.is_zero()),
+        Err(error) => Some(error.duration() + interval),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_wait() {
+        let interval = Duration::from_secs(5);
+        let now = UNIX_EPOCH + Duration::from_secs(100);
+
+        assert_eq!(remaining_wait(None, now, interval), None);
+        assert_eq!(
+            remaining_wait(Some(now - Duration::from_secs(2)), now, interval),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            remaining_wait(Some(now - Duration::from_secs(5)), now, interval),
+            None
+        );
+        assert_eq!(
+            remaining_wait(Some(now - Duration::from_secs(60)), now, interval),
+            None
+        );
+        assert_eq!(
+            remaining_wait(Some(now + Duration::from_secs(3)), now, interval),
+            Some(Duration::from_secs(8))
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::puzzle::PuzzleId;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use std::thread;
+
+/// source of the current time, so waiting can be tested without actually waiting
+pub trait Clock {
+    fn now(&self) -> DateTime<Utc>;
+
+    fn sleep(&self, duration: Duration);
+}
+
+/// the real clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        if let Ok(duration) = duration.to_std() {
+            thread::sleep(duration);
+        }
+    }
+}
+
+/// instant a puzzle unlocks, midnight US Eastern time (UTC-5 in December)
+///
+/// Puzzles on dates that do not exist never unlock.
+pub fn unlock_time(PuzzleId { year, day }: PuzzleId) -> DateTime<Utc> {
+    i32::try_from(year)
+        .ok()
+        .and_then(|year| Utc.with_ymd_and_hms(year, 12, day, 5, 0, 0).single())
+        .unwrap_or(DateTime::<Utc>::MAX_UTC)
+}
+
+/// wait until a puzzle is unlocked
+///
+/// `on_tick` is called with the remaining time about once per second.
+pub fn wait_for_unlock(puzzle: PuzzleId, clock: &impl Clock, mut on_tick: impl FnMut(Duration)) {
+    let unlock = unlock_time(puzzle);
+
+    loop {
+        let remaining = unlock - clock.now();
+        if remaining <= Duration::zero() {
+            return;
+        }
+
+        on_tick(remaining);
+
+        // sleep to the next full second of the countdown
+        let step =
+            remaining - Duration::seconds((remaining - Duration::milliseconds(1)).num_seconds());
+        clock.sleep(step.min(Duration::seconds(1)));
+    }
+}
+
+/// format the remaining time like `1d 02:03:04`
+pub fn format_countdown(remaining: Duration) -> String {
+    let seconds = (remaining.num_milliseconds() + 999) / 1000;
+    let days = seconds / 86400;
+    let time = format!(
+        "{:0>2}:{:0>2}:{:0>2}",
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60
+    );
+
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    struct FakeClock {
+        now: Cell<DateTime<Utc>>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    fn puzzle(day: u32) -> PuzzleId {
+        PuzzleId { year: 2022, day }
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(
+            unlock_time(puzzle(17)),
+            Utc.with_ymd_and_hms(2022, 12, 17, 5, 0, 0).unwrap()
+        );
+        assert_eq!(unlock_time(puzzle(32)), DateTime::<Utc>::MAX_UTC);
+    }
+
+    #[test]
+    fn test_wait_for_unlock() {
+        let clock = FakeClock {
+            now: Cell::new(unlock_time(puzzle(1)) - Duration::milliseconds(2500)),
+            sleeps: RefCell::new(Vec::new()),
+        };
+        let mut ticks = Vec::new();
+
+        wait_for_unlock(puzzle(1), &clock, |remaining| ticks.push(remaining));
+
+        assert_eq!(clock.now(), unlock_time(puzzle(1)));
+        assert_eq!(
+            ticks,
+            vec![
+                Duration::milliseconds(2500),
+                Duration::seconds(2),
+                Duration::seconds(1)
+            ]
+        );
+        assert_eq!(clock.sleeps.borrow()[0], Duration::milliseconds(500));
+    }
+
+    #[test]
+    fn test_wait_for_unlock_after_unlock() {
+        let clock = FakeClock {
+            now: Cell::new(unlock_time(puzzle(1)) + Duration::seconds(1)),
+            sleeps: RefCell::new(Vec::new()),
+        };
+
+        wait_for_unlock(puzzle(1), &clock, |_| panic!("puzzle is already unlocked"));
+
+        assert!(clock.sleeps.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(Duration::milliseconds(2500)), "00:00:03");
+        assert_eq!(
+            format_countdown(Duration::hours(26) + Duration::seconds(4)),
+            "1d 02:00:04"
+        );
+    }
+}
@@ -0,0 +1,54 @@
+// {{year}} day {{day}}: {{title}}
+use aoc::client::get_input;
+use std::error::Error;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let input = get_input({{year}}, {{day}})?;
+
+{{bench}}
+    println!("solution part 1: {}", part_1(&input)?);
+    println!("solution part 2: {}", part_2(&input)?);
+
+    Ok(())
+}
+
+fn parse(input: &str) -> Result<Vec<&str>, Box<dyn Error>> {
+    Ok(input.lines().collect())
+}
+
+pub fn part_1(input: &str) -> Result<String, Box<dyn Error>> {
+    let lines = parse(input)?;
+
+    unimplemented!();
+}
+
+pub fn part_2(input: &str) -> Result<String, Box<dyn Error>> {
+    let lines = parse(input)?;
+
+    unimplemented!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    {{todo_example}}
+    const TEST_INPUT: &str = "{{example}}";
+
+    #[test]
+    fn test_parse() {
+        assert!(!parse(TEST_INPUT).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_part_1() {
+        {{todo_answer_1}}
+        assert_eq!(part_1(TEST_INPUT).unwrap(), "{{answer_1}}")
+    }
+
+    #[test]
+    fn test_part_2() {
+        {{todo_answer_2}}
+        assert_eq!(part_2(TEST_INPUT).unwrap(), "{{answer_2}}")
+    }
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use aoc::client::{
+    cache_root, clear_cache, get_cache_index, get_description, get_history, get_input, verify_cache,
+};
+use common::{setup_test_env, SESSION};
+use std::{env, fs, path::PathBuf};
+
+/// clearing the cache affects every entry, so all cases run in one test
+#[test]
+fn test_cache_index() {
+    let (_server, cache) = setup_test_env("cache");
+    env::set_var("AOC_SESSION_ALICE", SESSION);
+
+    assert_eq!(cache_root(), cache);
+
+    get_input(2022, 1).unwrap();
+    get_description(2022, 1).unwrap();
+    env::set_var("AOC_PROFILE", "alice");
+    get_input(2022, 2).unwrap();
+    env::remove_var("AOC_PROFILE");
+
+    let index = get_cache_index().unwrap();
+    let files = index.entries.keys().cloned().collect::<Vec<_>>();
+    assert_eq!(
+        files,
+        [
+            "2022_01.html",
+            "2022_01.md",
+            "2022_01.txt",
+            "alice/2022_02.txt"
+        ]
+        .map(PathBuf::from)
+    );
+    assert_eq!(index.entries[&files[0]].profile, "default");
+    assert_eq!(index.entries[&files[3]].profile, "alice");
+
+    assert!(verify_cache().unwrap().is_empty());
+
+    fs::write(cache.join("2022_01.txt"), "<html>Please log in</html>").unwrap();
+    fs::remove_file(cache.join("2022_01.html")).unwrap();
+
+    let problems = verify_cache().unwrap();
+    assert_eq!(problems.len(), 2);
+    assert_eq!(problems[0].0, PathBuf::from("2022_01.html"));
+    assert_eq!(problems[0].1, "file is missing");
+    assert_eq!(problems[1].0, PathBuf::from("2022_01.txt"));
+
+    // the history is not a download and survives clearing
+    fs::write(cache.join("2022_01.log"), "").unwrap();
+
+    assert_eq!(clear_cache(false).unwrap().len(), 3);
+    assert!(!cache.join("2022_01.md").exists());
+    assert!(cache.join("alice/2022_02.txt").exists());
+    assert!(get_history(2022, 1).unwrap().submissions.is_empty());
+    assert!(cache.join("2022_01.log").exists());
+    assert_eq!(get_cache_index().unwrap().entries.len(), 1);
+
+    assert_eq!(clear_cache(true).unwrap().len(), 1);
+    assert!(!cache.join("alice/2022_02.txt").exists());
+    assert!(get_cache_index().unwrap().entries.is_empty());
+}
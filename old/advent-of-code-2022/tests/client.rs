@@ -0,0 +1,271 @@
+mod common;
+
+use aoc::{
+    client::{
+        check_auth, get_answers, get_calendar, get_description, get_history, get_input,
+        get_leaderboard, harvest_answers, invalidate_input, prefetch_year, record_answer,
+        submit_answer, ClientError, FetchStatus, Verdict,
+    },
+    history::Rejection,
+    puzzle::PuzzleId,
+};
+use common::{fixtures, setup_test_env, test_cache, FakeAoc};
+use std::{fs, path::PathBuf, sync::OnceLock, time::Duration};
+
+/// start the fake server once and point the client to it
+fn setup() -> &'static FakeAoc {
+    static SERVER: OnceLock<FakeAoc> = OnceLock::new();
+
+    SERVER.get_or_init(|| setup_test_env("client").0)
+}
+
+fn cache_folder() -> PathBuf {
+    test_cache("client")
+}
+
+#[test]
+fn test_get_input_is_cached() {
+    let server = setup();
+    let expected = fs::read_to_string(fixtures().join("2022/day/1/input")).unwrap();
+
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+
+    assert_eq!(server.request_count("GET /2022/day/1/input"), 1);
+    assert!(cache_folder().join("2022_01.txt").exists());
+}
+
+#[test]
+fn test_get_input_replaces_invalid_cache() {
+    let server = setup();
+    let expected = fs::read_to_string(fixtures().join("2022/day/2/input")).unwrap();
+
+    fs::create_dir_all(cache_folder()).unwrap();
+    fs::write(cache_folder().join("2022_02.txt"), "<!DOCTYPE html>").unwrap();
+
+    assert_eq!(get_input(2022, 2).unwrap(), expected);
+    assert_eq!(server.request_count("GET /2022/day/2/input"), 1);
+
+    invalidate_input(2022, 2).unwrap();
+    assert!(!cache_folder().join("2022_02.txt").exists());
+}
+
+#[test]
+fn test_get_input_not_unlocked() {
+    setup();
+
+    assert!(matches!(get_input(2022, 24), Err(ClientError::NotUnlocked)));
+    assert!(!cache_folder().join("2022_24.txt").exists());
+}
+
+#[test]
+fn test_get_input_not_found() {
+    setup();
+
+    assert!(matches!(get_input(2022, 26), Err(ClientError::NotFound)));
+    assert!(!cache_folder().join("2022_26.txt").exists());
+}
+
+#[test]
+fn test_get_input_session_expired() {
+    setup();
+
+    assert!(matches!(
+        get_input(2022, 22),
+        Err(ClientError::SessionExpired)
+    ));
+    assert!(!cache_folder().join("2022_22.txt").exists());
+}
+
+#[test]
+fn test_get_input_retries_transient_errors() {
+    let server = setup();
+
+    assert!(get_input(2022, 23).is_ok());
+    assert_eq!(server.request_count("GET /2022/day/23/input"), 2);
+}
+
+#[test]
+fn test_get_description() {
+    setup();
+
+    let description = get_description(2022, 1).unwrap();
+
+    assert!(description.starts_with("## --- Day 1: Calorie Counting ---"));
+    assert!(description.contains("## --- Part Two ---"));
+    assert!(cache_folder().join("2022_01.md").exists());
+}
+
+#[test]
+fn test_submit_answer() {
+    setup();
+
+    assert_eq!(submit_answer(2022, 1, 1, "high").unwrap(), Verdict::TooHigh);
+    assert_eq!(submit_answer(2022, 1, 1, "low").unwrap(), Verdict::TooLow);
+    assert_eq!(submit_answer(2022, 1, 1, "42").unwrap(), Verdict::Wrong);
+    assert_eq!(
+        submit_answer(2022, 1, 1, "correct").unwrap(),
+        Verdict::Correct
+    );
+    assert_eq!(
+        submit_answer(2022, 1, 2, "wait").unwrap(),
+        Verdict::RateLimited(Duration::from_secs(65))
+    );
+    assert_eq!(
+        submit_answer(2022, 1, 2, "solved").unwrap(),
+        Verdict::AlreadySolved
+    );
+
+    let history = get_history(2022, 1).unwrap();
+    assert_eq!(history.submissions.len(), 6);
+    assert_eq!(history.submissions[3].answer, "correct");
+    assert_eq!(history.submissions[3].verdict, Verdict::Correct);
+
+    assert_eq!(get_answers(2022).unwrap().get(1, 1), None);
+    record_answer(2022, 1, 1, "correct").unwrap();
+    assert_eq!(get_answers(2022).unwrap().get(1, 1), Some("correct"));
+}
+
+#[test]
+fn test_submit_answer_is_not_retried() {
+    let server = setup();
+
+    assert!(matches!(
+        submit_answer(2022, 4, 1, "42"),
+        Err(ClientError::Status(status)) if status.as_u16() == 503
+    ));
+    assert_eq!(server.request_count("POST /2022/day/4/answer"), 1);
+}
+
+#[test]
+fn test_submit_answer_rejects_known_bad_answers() {
+    let server = setup();
+
+    assert_eq!(submit_answer(2022, 3, 1, "1000").unwrap(), Verdict::TooHigh);
+    assert_eq!(submit_answer(2022, 3, 1, "10").unwrap(), Verdict::TooLow);
+    assert_eq!(submit_answer(2022, 3, 1, "500").unwrap(), Verdict::Wrong);
+
+    for (answer, rejection) in [
+        ("1500", Rejection::TooHigh(1000)),
+        ("5", Rejection::TooLow(10)),
+        ("500", Rejection::AlreadyWrong),
+    ] {
+        assert!(matches!(
+            submit_answer(2022, 3, 1, answer),
+            Err(ClientError::Rejected(r)) if r == rejection
+        ));
+    }
+
+    assert_eq!(server.request_count("POST /2022/day/3/answer"), 3);
+}
+
+#[test]
+fn test_get_calendar() {
+    let server = setup();
+
+    let calendar = get_calendar(2021).unwrap();
+
+    assert_eq!(calendar.stars[0], Some(2));
+    assert_eq!(calendar.stars[6], Some(1));
+    assert_eq!(calendar.stars[7], Some(0));
+    assert_eq!(calendar.stars[24], None);
+    assert_eq!(calendar.total(), 18);
+
+    assert_eq!(get_calendar(2021).unwrap(), calendar);
+    assert_eq!(server.request_count("GET /2021"), 1);
+}
+
+#[test]
+fn test_harvest_answers() {
+    let server = setup();
+
+    assert!(get_answers(2020).unwrap().is_empty());
+
+    let answers = harvest_answers(2020).unwrap();
+
+    assert_eq!(
+        answers.to_string(),
+        "1 1 1007104\n1 2 18847752\n2 1 546\n25 1 6011069\n"
+    );
+    assert_eq!(get_answers(2020).unwrap(), answers);
+
+    // days with all answers known are not downloaded again
+    assert_eq!(harvest_answers(2020).unwrap(), answers);
+    assert_eq!(server.request_count("GET /2020/day/1"), 1);
+    assert_eq!(server.request_count("GET /2020/day/2"), 1);
+    assert_eq!(server.request_count("GET /2020/day/3"), 0);
+    assert_eq!(server.request_count("GET /2020/day/25"), 1);
+}
+
+#[test]
+fn test_prefetch_year() {
+    let server = setup();
+
+    get_input(2019, 1).unwrap();
+
+    let fetches = prefetch_year(2019).unwrap();
+
+    assert_eq!(
+        fetches.iter().map(|fetch| fetch.day).collect::<Vec<_>>(),
+        [1, 2, 3]
+    );
+    assert!(matches!(fetches[0].input, FetchStatus::Skipped));
+    assert!(matches!(fetches[0].description, FetchStatus::Fetched));
+    assert!(matches!(fetches[1].input, FetchStatus::Fetched));
+    assert!(matches!(fetches[1].description, FetchStatus::Fetched));
+    assert!(matches!(
+        fetches[2].input,
+        FetchStatus::Failed(ClientError::NotFound)
+    ));
+    assert!(matches!(fetches[2].description, FetchStatus::Fetched));
+    assert_eq!(
+        get_input(2019, 2).unwrap(),
+        fs::read_to_string(fixtures().join("2019/day/2/input")).unwrap()
+    );
+
+    let fetches = prefetch_year(2019).unwrap();
+
+    assert!(fetches[..2]
+        .iter()
+        .all(|fetch| matches!(fetch.input, FetchStatus::Skipped)));
+    assert!(fetches
+        .iter()
+        .all(|fetch| matches!(fetch.description, FetchStatus::Skipped)));
+    assert_eq!(server.request_count("GET /2019/day/1/input"), 1);
+    assert_eq!(server.request_count("GET /2019/day/2"), 1);
+}
+
+#[test]
+fn test_get_leaderboard() {
+    let server = setup();
+
+    let leaderboard = get_leaderboard(2022, 12345).unwrap();
+
+    assert_eq!(leaderboard.members.len(), 3);
+    let ranking = leaderboard.ranking();
+    assert_eq!(ranking[0].display_name(), "Ada Lovelace");
+    assert_eq!(ranking[0].local_score, 22);
+    assert_eq!(ranking[1].display_name(), "(anonymous user #67890)");
+    assert_eq!(ranking[2].stars_on(1), 0);
+    assert_eq!(
+        ranking[0].time_to_star(PuzzleId::new(2022, 1).unwrap(), 2),
+        Some(chrono::Duration::minutes(10))
+    );
+
+    assert_eq!(get_leaderboard(2022, 12345).unwrap(), leaderboard);
+    assert_eq!(
+        server.request_count("GET /2022/leaderboard/private/view/12345.json"),
+        1
+    );
+}
+
+#[test]
+fn test_check_auth() {
+    setup();
+
+    let auth = check_auth().unwrap();
+
+    assert_eq!(auth.user, "test user");
+    assert_eq!(check_auth().unwrap().first_seen, auth.first_seen);
+    assert!(auth.expires > auth.first_seen);
+}
@@ -0,0 +1,204 @@
+//! Local stand-in for adventofcode.com used by the integration tests.
+//!
+//! Responses are served from `tests/fixtures`:
+//!
+//! - `GET /path` serves `fixtures/path`, or `fixtures/path/index.html`
+//! - a fixture named `path.404` is served with that status code instead
+//! - `POST /{year}/day/{day}/answer` serves `fixtures/answer/{answer}.html`
+//! - unknown paths get a 404 page
+//!
+//! Requests without the session cookie `session=test-session` are treated like
+//! requests of a logged out user. Paths in `FLAKY_PATHS` fail once with a 503.
+
+#![allow(dead_code)]
+
+use std::{
+    collections::HashSet,
+    env, fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    thread,
+};
+
+pub const SESSION: &str = "test-session";
+
+const FLAKY_PATHS: &[&str] = &["/2022/day/23/input", "/2022/day/4/answer"];
+
+#[derive(Debug, Default)]
+struct State {
+    requests: Vec<String>,
+    failed: HashSet<String>,
+}
+
+pub struct FakeAoc {
+    pub url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeAoc {
+    /// start the server on a random local port
+    pub fn start() -> FakeAoc {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let server_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let state = Arc::clone(&server_state);
+                thread::spawn(move || handle(stream, &state));
+            }
+        });
+
+        FakeAoc { url, state }
+    }
+
+    /// number of requests received for a method and path, e.g. `GET /2022/day/1/input`
+    pub fn request_count(&self, request: &str) -> usize {
+        let state = self.state.lock().unwrap();
+
+        state.requests.iter().filter(|r| *r == request).count()
+    }
+}
+
+/// cache folder of a test file, in the temporary folder
+pub fn test_cache(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("aoc-{name}-test-{}", std::process::id()))
+}
+
+/// start a fake server and point the client to it, with an empty cache and no config file
+///
+/// The client is configured through the environment, so call this once per test file.
+pub fn setup_test_env(name: &str) -> (FakeAoc, PathBuf) {
+    let server = FakeAoc::start();
+    let cache = test_cache(name);
+    fs::remove_dir_all(&cache).ok();
+    fs::create_dir_all(&cache).unwrap();
+
+    env::set_var("AOC_BASE_URL", &server.url);
+    env::set_var("AOC_CACHE_DIR", &cache);
+    env::set_var("AOC_CONFIG", cache.join("config"));
+    env::set_var("AOC_THROTTLE_INTERVAL", "0");
+    env::set_var("AOC_SESSION", SESSION);
+
+    (server, cache)
+}
+
+pub fn fixtures() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+fn handle(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    let mut logged_in = false;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        let (name, value) = line.split_once(':').unwrap_or_default();
+        match name.to_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().unwrap_or(0),
+            "cookie" => logged_in = value.contains(&format!("session={SESSION}")),
+            _ => (),
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).ok();
+    let body = String::from_utf8_lossy(&body);
+
+    let (status, response) = respond(&method, &path, &body, logged_in, state);
+
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {status} {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        reason(status),
+        response.len()
+    )
+    .ok();
+}
+
+fn respond(
+    method: &str,
+    path: &str,
+    body: &str,
+    logged_in: bool,
+    state: &Mutex<State>,
+) -> (u16, String) {
+    let mut state = state.lock().unwrap();
+    state.requests.push(format!("{method} {path}"));
+
+    if FLAKY_PATHS.contains(&path) && state.failed.insert(path.to_string()) {
+        return (503, String::from("Service Unavailable"));
+    }
+
+    if !logged_in && path.ends_with("/input") {
+        return (
+            400,
+            String::from(
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n",
+            ),
+        );
+    }
+
+    if method == "POST" && path.ends_with("/answer") {
+        let answer = body
+            .split('&')
+            .find_map(|field| field.strip_prefix("answer="))
+            .unwrap_or_default();
+
+        return read_fixture(&format!("answer/{answer}.html"))
+            .or_else(|| read_fixture("answer/wrong.html"))
+            .unwrap();
+    }
+
+    let fixture = path.trim_start_matches('/');
+    read_fixture(fixture)
+        .or_else(|| read_fixture(&format!("{fixture}/index.html")))
+        .or_else(|| read_fixture("404.html").map(|(_, page)| (404, page)))
+        .unwrap()
+}
+
+/// read a fixture, together with the status code it is served with
+fn read_fixture(name: &str) -> Option<(u16, String)> {
+    let path = fixtures().join(name);
+
+    if path.is_file() {
+        return fs::read_to_string(path).ok().map(|body| (200, body));
+    }
+
+    let file_name = path.file_name()?.to_str()?.to_string();
+    fs::read_dir(path.parent()?)
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let status = name.strip_prefix(&format!("{file_name}."))?.parse().ok()?;
+
+            Some((status, fs::read_to_string(entry.path()).ok()?))
+        })
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
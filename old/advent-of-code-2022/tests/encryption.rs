@@ -0,0 +1,65 @@
+mod common;
+
+use aoc::{
+    client::{encrypt_input_cache, get_input, ClientError},
+    encryption::{is_encrypted, EncryptionError},
+};
+use common::{fixtures, setup_test_env};
+use std::{env, fs};
+
+/// the key is configured through the environment, so all cases run in one test
+#[test]
+fn test_encrypted_input_cache() {
+    let (server, cache) = setup_test_env("encryption");
+
+    let expected = fs::read_to_string(fixtures().join("2022/day/1/input")).unwrap();
+
+    // plaintext cache written without a key is migrated
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+    assert_eq!(
+        fs::read_to_string(cache.join("2022_01.txt")).unwrap(),
+        expected
+    );
+
+    assert!(matches!(
+        encrypt_input_cache(),
+        Err(ClientError::Encryption(EncryptionError::MissingKey))
+    ));
+
+    env::set_var("AOC_CACHE_PASSPHRASE", "correct horse");
+
+    assert_eq!(
+        encrypt_input_cache().unwrap(),
+        vec![cache.join("2022_01.txt")]
+    );
+    assert!(is_encrypted(&fs::read(cache.join("2022_01.txt")).unwrap()));
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+
+    // new downloads are encrypted right away
+    assert_eq!(
+        get_input(2022, 2).unwrap(),
+        fs::read_to_string(fixtures().join("2022/day/2/input")).unwrap()
+    );
+    assert!(is_encrypted(&fs::read(cache.join("2022_02.txt")).unwrap()));
+
+    // encrypted entries are kept when they cannot be decrypted
+    env::set_var("AOC_CACHE_PASSPHRASE", "battery staple");
+    assert!(matches!(
+        get_input(2022, 1),
+        Err(ClientError::Encryption(EncryptionError::WrongKey))
+    ));
+
+    env::remove_var("AOC_CACHE_PASSPHRASE");
+    assert!(matches!(
+        get_input(2022, 1),
+        Err(ClientError::Encryption(EncryptionError::MissingKey))
+    ));
+
+    // a key file works as well
+    let key_file = cache.join("key");
+    fs::write(&key_file, "correct horse").unwrap();
+    env::set_var("AOC_CACHE_KEY_FILE", &key_file);
+
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+    assert_eq!(server.request_count("GET /2022/day/1/input"), 1);
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use aoc::client::{get_input, ClientError};
+use common::{fixtures, setup_test_env, SESSION};
+use std::{env, fs};
+
+/// profiles are selected through the environment, so all cases run in one test
+#[test]
+fn test_profiles_use_own_session_and_cache() {
+    let (_server, cache) = setup_test_env("profile");
+    env::remove_var("AOC_SESSION");
+    env::set_var("AOC_SESSION_ALICE", SESSION);
+
+    let expected = fs::read_to_string(fixtures().join("2022/day/1/input")).unwrap();
+
+    assert!(matches!(
+        get_input(2022, 1),
+        Err(ClientError::MissingSession(variable)) if variable == "AOC_SESSION"
+    ));
+
+    env::set_var("AOC_PROFILE", "alice");
+
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+    assert!(cache.join("alice/2022_01.txt").exists());
+    assert!(!cache.join("2022_01.txt").exists());
+
+    // sessions of profiles without environment variable are read from the config file
+    fs::write(
+        cache.join("config"),
+        format!("session = invalid\n\n[carol]\nsession = {SESSION}\n"),
+    )
+    .unwrap();
+
+    env::set_var("AOC_PROFILE", "bob");
+
+    assert!(matches!(
+        get_input(2022, 1),
+        Err(ClientError::MissingSession(variable)) if variable == "AOC_SESSION_BOB"
+    ));
+
+    env::set_var("AOC_PROFILE", "carol");
+
+    assert_eq!(get_input(2022, 1).unwrap(), expected);
+    assert!(cache.join("carol/2022_01.txt").exists());
+
+    // an invalid profile is an error instead of falling back to another account
+    env::set_var("AOC_PROFILE", "../carol");
+
+    assert!(matches!(
+        get_input(2022, 1),
+        Err(ClientError::InvalidProfile(_))
+    ));
+}
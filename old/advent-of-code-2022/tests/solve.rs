@@ -0,0 +1,54 @@
+use std::{env, path::Path, process::Command};
+
+/// run `aoc solve` with the solutions compiled into the aoc binary
+fn solve(puzzle: &str, input: &str) -> (bool, String, String) {
+    let folder = env::temp_dir().join(format!("aoc-solve-test-{}", std::process::id()));
+    let input = Path::new(env!("CARGO_MANIFEST_DIR")).join(input);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["solve", puzzle, "--input"])
+        .arg(input)
+        .env("AOC_CACHE_DIR", &folder)
+        .env("AOC_CONFIG", folder.join("config"))
+        .env("AOC_BASE_URL", "http://127.0.0.1:1")
+        .env_remove("AOC_PROFILE")
+        .output()
+        .unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).into_owned(),
+        String::from_utf8_lossy(&output.stderr).into_owned(),
+    )
+}
+
+#[test]
+fn test_registered_solution() {
+    let (success, stdout, _) = solve("2022-1", "tests/fixtures/2022/day/1/input");
+
+    assert!(success);
+    assert!(stdout.contains("solution part 1: 24000\n"), "{stdout}");
+    assert!(stdout.contains("solution part 2: 45000\n"), "{stdout}");
+}
+
+#[test]
+fn test_unimplemented_solution() {
+    let (success, _, stderr) = solve("2022-7", "tests/fixtures/2022/day/1/input");
+
+    assert!(!success);
+    assert!(
+        stderr.contains("error: part 1 failed: not implemented"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_unknown_solution() {
+    let (success, _, stderr) = solve("2015-1", "tests/fixtures/2022/day/1/input");
+
+    assert!(!success);
+    assert!(
+        stderr.contains("error: no solution for 2015-1 in src/bin"),
+        "{stderr}"
+    );
+}